@@ -0,0 +1,4 @@
+pub use self::get_events::get_events;
+pub use self::ics::{CalendarEvent, EventOccurrence, parse_ics, expand_events};
+mod get_events;
+mod ics;
@@ -0,0 +1,72 @@
+use crate::calendar::{CalendarEvent, EventOccurrence, parse_ics, expand_events};
+use crate::misc::{FeedError, get_current_dir};
+use chrono::{DateTime, Duration, Local};
+use reqwest::{Client, Response, StatusCode, Url};
+use std::fs::read_to_string;
+use std::path::PathBuf;
+
+/// The next `max_events` events from every calendar, looking `days` ahead.
+/// Calendars that can't be read are skipped so one bad link doesn't empty the list.
+pub async fn get_events(calendars: &[String], max_events: usize, days: i64) -> Result<Vec<EventOccurrence>, FeedError> {
+    let client: Client = Client::builder().use_rustls_tls().build().unwrap();
+
+    let mut events: Vec<CalendarEvent> = Vec::new();
+    let mut fetch_error: Option<FeedError> = None;
+    for calendar in calendars {
+        let ics: String = match read_calendar(&client, calendar).await {
+            Ok(x) => x,
+            Err(calendar_error) => {
+                println!("Error getting calendar {}: {}", calendar, calendar_error);
+                fetch_error = Some(calendar_error);
+                continue;
+            }
+        };
+
+        match parse_ics(&ics) {
+            Ok(calendar_events) => events.extend(calendar_events),
+            Err(parse_error) => {
+                println!("Error reading calendar {}: {}", calendar, parse_error);
+                fetch_error = Some(parse_error);
+            }
+        }
+    }
+
+    // Only an error when there was nothing to show.
+    if let (true, Some(calendar_error)) = (events.is_empty(), fetch_error) {
+        return Err(calendar_error);
+    }
+
+    let from: DateTime<Local> = Local::now();
+    let mut occurrences: Vec<EventOccurrence> = expand_events(&events, from, from + Duration::days(days));
+    occurrences.truncate(max_events);
+
+    Ok(occurrences)
+}
+
+/// Calendars are urls, including the webcal links calendar apps share, or paths relative to the executable.
+async fn read_calendar(client: &Client, calendar: &str) -> Result<String, FeedError> {
+    let calendar_url: Option<String> = match calendar.split_once("://") {
+        Some(("webcal", rest)) => Some(format!("https://{rest}")),
+        Some(("http", _)) | Some(("https", _)) => Some(calendar.to_string()),
+        _ => None
+    };
+
+    let calendar_url: String = match calendar_url {
+        Some(x) => x,
+        None => {
+            let file_path: PathBuf = get_current_dir().join(calendar);
+            return Ok(read_to_string(file_path)?);
+        }
+    };
+
+    let escaped_url: Url = match Url::parse(calendar_url.as_str()) {
+        Ok(url) => url,
+        Err(url_error) => return Err(FeedError::Network { msg: url_error.to_string() })
+    };
+
+    let calendar_response: Response = client.get(escaped_url).send().await?;
+    match calendar_response.status() {
+        StatusCode::OK => Ok(calendar_response.text().await?),
+        status_code => Err(FeedError::HttpStatus { http_code: status_code })
+    }
+}
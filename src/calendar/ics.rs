@@ -0,0 +1,699 @@
+use chrono::{DateTime, Datelike, Duration, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+use crate::misc::FeedError;
+
+/// Enough for a daily event that started decades ago.
+const MAX_PERIODS: i64 = 50_000;
+
+/// How an event's wall clock time maps to an instant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventZone {
+    Utc,
+    /// No time zone given, or a date, so it's the same wall clock time wherever the mirror is.
+    Floating,
+    Named(Tz),
+}
+
+impl EventZone {
+    /// Times skipped by a daylight saving change are moved an hour later, repeated times use the first.
+    pub fn resolve(&self, time: NaiveDateTime) -> Option<DateTime<Local>> {
+        match self {
+            EventZone::Utc => Some(Utc.from_utc_datetime(&time).with_timezone(&Local)),
+            EventZone::Floating => from_wall_clock(&Local, time),
+            EventZone::Named(tz) => from_wall_clock(tz, time).map(|x| x.with_timezone(&Local)),
+        }
+    }
+}
+
+fn from_wall_clock<Z: TimeZone>(zone: &Z, time: NaiveDateTime) -> Option<DateTime<Z>> {
+    match zone.from_local_datetime(&time) {
+        LocalResult::Single(x) => Some(x),
+        LocalResult::Ambiguous(earliest, _) => Some(earliest),
+        LocalResult::None => match zone.from_local_datetime(&(time + Duration::hours(1))) {
+            LocalResult::Single(x) => Some(x),
+            LocalResult::Ambiguous(earliest, _) => Some(earliest),
+            LocalResult::None => None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The parts of RRULE that calendar apps actually write.
+#[derive(Debug, Clone)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<DateTime<Local>>,
+    /// Weekdays, with an optional position in the month or year like the -1 in `-1FR`.
+    pub by_day: Vec<(Option<i32>, Weekday)>,
+    pub by_month_day: Vec<i32>,
+    pub by_month: Vec<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub summary: String,
+    pub start: NaiveDateTime,
+    pub zone: EventZone,
+    pub all_day: bool,
+    pub duration: Duration,
+    pub rule: Option<RecurrenceRule>,
+    pub exdates: Vec<DateTime<Local>>,
+    /// Set on an edited copy of one occurrence of a recurring event.
+    pub recurrence_id: Option<DateTime<Local>>,
+    pub cancelled: bool,
+}
+
+/// One occurrence of an event, in the mirror's time zone.
+#[derive(Debug, Clone)]
+pub struct EventOccurrence {
+    pub summary: String,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub all_day: bool,
+}
+
+/// Reads the VEVENTs out of an iCalendar file. Events that can't be read are skipped.
+pub fn parse_ics(ics: &str) -> Result<Vec<CalendarEvent>, FeedError> {
+    if !ics.trim_start().starts_with("BEGIN:VCALENDAR") {
+        return Err(FeedError::Parse { msg: "Not an iCalendar file.".to_string() });
+    }
+
+    let mut events: Vec<CalendarEvent> = Vec::new();
+    let mut event_lines: Option<Vec<ContentLine>> = None;
+    for line in unfold_lines(ics) {
+        let content_line: ContentLine = match ContentLine::parse(&line) {
+            Some(x) => x,
+            None => continue
+        };
+
+        match (content_line.name.as_str(), content_line.value.as_str()) {
+            ("BEGIN", "VEVENT") => event_lines = Some(Vec::new()),
+            ("END", "VEVENT") => {
+                if let Some(lines) = event_lines.take() {
+                    match parse_event(&lines) {
+                        Ok(x) => events.push(x),
+                        Err(event_error) => println!("Skipping calendar event: {}", event_error)
+                    }
+                }
+            },
+            _ => {
+                if let Some(lines) = event_lines.as_mut() {
+                    lines.push(content_line);
+                }
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+/// Every occurrence that overlaps `from` to `to`, with edited occurrences in place of the originals.
+pub fn expand_events(events: &[CalendarEvent], from: DateTime<Local>, to: DateTime<Local>) -> Vec<EventOccurrence> {
+    let mut occurrences: Vec<EventOccurrence> = Vec::new();
+    for event in events.iter().filter(|event| !event.cancelled) {
+        // Edits and cancellations of single occurrences replace that occurrence of the series.
+        let replaced: Vec<DateTime<Local>> = events.iter()
+            .filter(|other| other.uid == event.uid && event.recurrence_id.is_none())
+            .filter_map(|other| other.recurrence_id)
+            .collect();
+
+        let starts: Vec<NaiveDateTime> = match (&event.rule, event.recurrence_id) {
+            (Some(rule), None) => get_recurrences(event, rule, to),
+            _ => vec![event.start]
+        };
+
+        for start in starts {
+            let start_time: DateTime<Local> = match event.zone.resolve(start) {
+                Some(x) => x,
+                None => continue
+            };
+            // Whole days are counted on the wall clock, hours and minutes are exact so an hour
+            // long event stays an hour long across a daylight saving change.
+            let end_time: DateTime<Local> = match event.duration.num_seconds() % 86_400 == 0 {
+                true => start.checked_add_signed(event.duration).and_then(|end| event.zone.resolve(end)).unwrap_or(start_time),
+                false => match start_time.checked_add_signed(event.duration) {
+                    Some(x) => x,
+                    None => continue
+                }
+            };
+
+            if event.exdates.contains(&start_time) || replaced.contains(&start_time) {
+                continue;
+            }
+            if end_time <= from && start_time < from || start_time >= to {
+                continue;
+            }
+
+            occurrences.push(EventOccurrence {
+                summary: event.summary.clone(),
+                start: start_time,
+                end: end_time,
+                all_day: event.all_day,
+            });
+        }
+    }
+
+    occurrences.sort_by(|a, b| a.start.cmp(&b.start).then(a.summary.cmp(&b.summary)));
+    occurrences
+}
+
+/// Wall clock start times from the rule, stepping in the event's own zone so an event
+/// at 9:00 stays at 9:00 across daylight saving changes.
+fn get_recurrences(event: &CalendarEvent, rule: &RecurrenceRule, to: DateTime<Local>) -> Vec<NaiveDateTime> {
+    let mut starts: Vec<NaiveDateTime> = Vec::new();
+    let start_date: NaiveDate = event.start.date();
+    let start_time: NaiveTime = event.start.time();
+    let interval: i64 = rule.interval.max(1) as i64;
+
+    for period in 0..MAX_PERIODS {
+        let (period_start, mut dates): (NaiveDate, Vec<NaiveDate>) = match rule.frequency {
+            Frequency::Daily => {
+                let day: NaiveDate = match Duration::try_days(period * interval).and_then(|x| start_date.checked_add_signed(x)) {
+                    Some(x) => x,
+                    None => break
+                };
+                let matches_day: bool = rule.by_day.is_empty() || rule.by_day.iter().any(|(_, weekday)| *weekday == day.weekday());
+                match matches_day && (rule.by_month.is_empty() || rule.by_month.contains(&day.month())) {
+                    true => (day, vec![day]),
+                    false => (day, Vec::new())
+                }
+            },
+            Frequency::Weekly => {
+                let first_week: NaiveDate = start_date - Duration::days(start_date.weekday().num_days_from_monday() as i64);
+                let week_start: NaiveDate = match Duration::try_weeks(period * interval).and_then(|x| first_week.checked_add_signed(x)) {
+                    Some(x) => x,
+                    None => break
+                };
+                let weekdays: Vec<Weekday> = match rule.by_day.is_empty() {
+                    true => vec![start_date.weekday()],
+                    false => rule.by_day.iter().map(|(_, weekday)| *weekday).collect()
+                };
+                (week_start, weekdays.iter().map(|weekday| week_start + Duration::days(weekday.num_days_from_monday() as i64)).collect())
+            },
+            Frequency::Monthly => {
+                let months: i64 = start_date.month0() as i64 + period * interval;
+                let year: i32 = match i32::try_from(months / 12).ok().and_then(|x| start_date.year().checked_add(x)) {
+                    Some(x) => x,
+                    None => break
+                };
+                let month: u32 = (months % 12) as u32 + 1;
+                match (NaiveDate::from_ymd_opt(year, month, 1), rule.by_month.is_empty() || rule.by_month.contains(&month)) {
+                    (Some(month_start), true) => (month_start, get_month_dates(year, month, rule, start_date)),
+                    (Some(month_start), false) => (month_start, Vec::new()),
+                    (None, _) => break
+                }
+            },
+            Frequency::Yearly => {
+                let year: i32 = match i32::try_from(period * interval).ok().and_then(|x| start_date.year().checked_add(x)) {
+                    Some(x) => x,
+                    None => break
+                };
+                let months: Vec<u32> = match rule.by_month.is_empty() {
+                    true => vec![start_date.month()],
+                    false => rule.by_month.clone()
+                };
+                match NaiveDate::from_ymd_opt(year, 1, 1) {
+                    Some(year_start) => (year_start, months.iter().flat_map(|month| get_month_dates(year, *month, rule, start_date)).collect()),
+                    None => break
+                }
+            }
+        };
+
+        if period_start > to.date_naive() + Duration::days(1) {
+            break;
+        }
+
+        dates.sort();
+        dates.dedup();
+        for date in dates.into_iter().filter(|date| *date >= start_date) {
+            let start: NaiveDateTime = date.and_time(start_time);
+            if let Some(until) = rule.until {
+                if event.zone.resolve(start).is_some_and(|x| x > until) {
+                    return starts;
+                }
+            }
+
+            starts.push(start);
+            if rule.count.is_some_and(|count| starts.len() >= count as usize) {
+                return starts;
+            }
+        }
+    }
+
+    starts
+}
+
+/// The days in one month picked by BYMONTHDAY or BYDAY, or the start's day of the month.
+fn get_month_dates(year: i32, month: u32, rule: &RecurrenceRule, start_date: NaiveDate) -> Vec<NaiveDate> {
+    let month_start: NaiveDate = match NaiveDate::from_ymd_opt(year, month, 1) {
+        Some(x) => x,
+        None => return Vec::new()
+    };
+    let month_length: i32 = match month_start.checked_add_months(chrono::Months::new(1)) {
+        Some(next_month) => (next_month - month_start).num_days() as i32,
+        None => return Vec::new()
+    };
+
+    if !rule.by_month_day.is_empty() {
+        return rule.by_month_day.iter()
+            .map(|day| match *day < 0 {
+                true => month_length + day + 1,
+                false => *day
+            })
+            .filter(|day| (1..=month_length).contains(day))
+            .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day as u32))
+            .collect();
+    }
+
+    if !rule.by_day.is_empty() {
+        let mut dates: Vec<NaiveDate> = Vec::new();
+        for (position, weekday) in &rule.by_day {
+            let matching: Vec<NaiveDate> = (0..month_length)
+                .map(|offset| month_start + Duration::days(offset as i64))
+                .filter(|date| date.weekday() == *weekday)
+                .collect();
+
+            match position {
+                Some(x) if *x > 0 => dates.extend(matching.get(*x as usize - 1)),
+                Some(x) if *x < 0 => dates.extend(matching.len().checked_sub(x.unsigned_abs() as usize).and_then(|index| matching.get(index))),
+                _ => dates.extend(matching)
+            }
+        }
+        return dates;
+    }
+
+    // Months without the start's day, like the 31st, are skipped.
+    NaiveDate::from_ymd_opt(year, month, start_date.day()).into_iter().collect()
+}
+
+struct ContentLine {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl ContentLine {
+    /// `NAME;PARAM=VALUE:value`, parameters can be quoted and contain colons.
+    fn parse(line: &str) -> Option<ContentLine> {
+        let mut in_quotes: bool = false;
+        let split_at: usize = line.char_indices().find(|(_, x)| {
+            if *x == '"' {
+                in_quotes = !in_quotes;
+            }
+            *x == ':' && !in_quotes
+        })?.0;
+
+        let (name_params, value): (&str, &str) = (&line[..split_at], &line[split_at + 1..]);
+        let mut parts = name_params.split(';');
+        let name: String = parts.next()?.trim().to_uppercase();
+        let params: Vec<(String, String)> = parts
+            .filter_map(|param| param.split_once('='))
+            .map(|(key, value)| (key.trim().to_uppercase(), value.trim_matches('"').to_string()))
+            .collect();
+
+        Some(ContentLine { name, params, value: value.to_string() })
+    }
+
+    fn param(&self, key: &str) -> Option<&str> {
+        self.params.iter().find(|(name, _)| name == key).map(|(_, value)| value.as_str())
+    }
+
+    /// Reads a DTSTART style value, returning the wall clock time, its zone and whether it's a whole day.
+    fn time(&self) -> Result<(NaiveDateTime, EventZone, bool), FeedError> {
+        parse_ics_time(&self.value, self.param("TZID"), self.param("VALUE") == Some("DATE"))
+    }
+}
+
+/// Long lines are folded onto following lines that start with a space or tab.
+fn unfold_lines(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.lines().map(|line| line.trim_end_matches('\r')) {
+        match (line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.to_string())
+        }
+    }
+
+    lines
+}
+
+fn parse_event(lines: &[ContentLine]) -> Result<CalendarEvent, FeedError> {
+    let start_line: &ContentLine = match lines.iter().find(|line| line.name == "DTSTART") {
+        Some(x) => x,
+        None => return Err(FeedError::Parse { msg: "Event has no DTSTART.".to_string() })
+    };
+    let (start, zone, all_day): (NaiveDateTime, EventZone, bool) = start_line.time()?;
+
+    let duration: Duration = match (lines.iter().find(|line| line.name == "DTEND"), lines.iter().find(|line| line.name == "DURATION")) {
+        (Some(end_line), _) => {
+            let (end, end_zone, _): (NaiveDateTime, EventZone, bool) = end_line.time()?;
+            match (zone.resolve(start), end_zone.resolve(end)) {
+                // Compared as instants in case the end is written in a different zone.
+                (Some(start_time), Some(end_time)) if end_zone != zone => end_time - start_time,
+                _ => end - start
+            }
+        },
+        (None, Some(duration_line)) => parse_duration(&duration_line.value)?,
+        (None, None) => match all_day {
+            true => Duration::days(1),
+            false => Duration::zero()
+        }
+    };
+    if start.checked_add_signed(duration).is_none() {
+        return Err(FeedError::Parse { msg: format!("Event ends out of range: {start}") });
+    }
+
+    let mut exdates: Vec<DateTime<Local>> = Vec::new();
+    for exdate_line in lines.iter().filter(|line| line.name == "EXDATE") {
+        for value in exdate_line.value.split(',') {
+            let (exdate, exdate_zone, _): (NaiveDateTime, EventZone, bool) = parse_ics_time(value, exdate_line.param("TZID"), exdate_line.param("VALUE") == Some("DATE"))?;
+            exdates.extend(exdate_zone.resolve(exdate));
+        }
+    }
+
+    let recurrence_id: Option<DateTime<Local>> = match lines.iter().find(|line| line.name == "RECURRENCE-ID") {
+        Some(recurrence_line) => {
+            let (recurrence_start, recurrence_zone, _): (NaiveDateTime, EventZone, bool) = recurrence_line.time()?;
+            recurrence_zone.resolve(recurrence_start)
+        },
+        None => None
+    };
+
+    let rule: Option<RecurrenceRule> = match lines.iter().find(|line| line.name == "RRULE") {
+        Some(rule_line) => Some(parse_rule(&rule_line.value, zone)?),
+        None => None
+    };
+
+    let get_text = |name: &str| -> String {
+        lines.iter().find(|line| line.name == name).map(|line| unescape_text(&line.value)).unwrap_or_default()
+    };
+
+    Ok(CalendarEvent {
+        uid: get_text("UID"),
+        summary: get_text("SUMMARY"),
+        start,
+        zone,
+        all_day,
+        duration,
+        rule,
+        exdates,
+        recurrence_id,
+        cancelled: get_text("STATUS").eq_ignore_ascii_case("CANCELLED"),
+    })
+}
+
+fn parse_ics_time(value: &str, tzid: Option<&str>, date_only: bool) -> Result<(NaiveDateTime, EventZone, bool), FeedError> {
+    let value: &str = value.trim();
+    if date_only || value.len() == 8 {
+        let date: NaiveDate = NaiveDate::parse_from_str(value, "%Y%m%d")?;
+        return Ok((date.and_time(NaiveTime::MIN), EventZone::Floating, true));
+    }
+
+    if let Some(utc_value) = value.strip_suffix('Z') {
+        return Ok((NaiveDateTime::parse_from_str(utc_value, "%Y%m%dT%H%M%S")?, EventZone::Utc, false));
+    }
+
+    let time: NaiveDateTime = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")?;
+    let zone: EventZone = match tzid {
+        Some(tz_name) => match tz_name.parse::<Tz>() {
+            Ok(tz) => EventZone::Named(tz),
+            Err(_) => {
+                println!("Unknown calendar time zone {tz_name}, using the mirror's.");
+                EventZone::Floating
+            }
+        },
+        None => EventZone::Floating
+    };
+
+    Ok((time, zone, false))
+}
+
+/// Durations look like `PT1H30M`, `P1D` or `P2W`.
+fn parse_duration(value: &str) -> Result<Duration, FeedError> {
+    let (negative, value): (bool, &str) = match value.trim().strip_prefix('-') {
+        Some(x) => (true, x),
+        None => (false, value.trim().trim_start_matches('+'))
+    };
+    let value: &str = match value.strip_prefix('P') {
+        Some(x) => x,
+        None => return Err(FeedError::Parse { msg: format!("Invalid duration: {value}") })
+    };
+
+    let mut duration: Duration = Duration::zero();
+    let mut number: String = String::new();
+    for x in value.chars() {
+        match x {
+            '0'..='9' => number.push(x),
+            'T' => continue,
+            unit => {
+                let amount: i64 = match number.parse::<i64>() {
+                    Ok(x) => x,
+                    Err(_) => return Err(FeedError::Parse { msg: format!("Invalid duration: {value}") })
+                };
+                let part: Option<Duration> = match unit {
+                    'W' => Duration::try_weeks(amount),
+                    'D' => Duration::try_days(amount),
+                    'H' => Duration::try_hours(amount),
+                    'M' => Duration::try_minutes(amount),
+                    'S' => Duration::try_seconds(amount),
+                    _ => return Err(FeedError::Parse { msg: format!("Invalid duration: {value}") })
+                };
+                duration = match part.and_then(|x| duration.checked_add(&x)) {
+                    Some(x) => x,
+                    None => return Err(FeedError::Parse { msg: format!("Duration out of range: {value}") })
+                };
+                number.clear();
+            }
+        }
+    }
+
+    match negative {
+        true => Ok(-duration),
+        false => Ok(duration)
+    }
+}
+
+fn parse_rule(value: &str, zone: EventZone) -> Result<RecurrenceRule, FeedError> {
+    let mut rule: RecurrenceRule = RecurrenceRule {
+        frequency: Frequency::Daily,
+        interval: 1,
+        count: None,
+        until: None,
+        by_day: Vec::new(),
+        by_month_day: Vec::new(),
+        by_month: Vec::new(),
+    };
+    let invalid_rule = || FeedError::Parse { msg: format!("Unsupported RRULE: {value}") };
+
+    for (key, part) in value.split(';').filter_map(|x| x.split_once('=')) {
+        match key.to_uppercase().as_str() {
+            "FREQ" => rule.frequency = match part.to_uppercase().as_str() {
+                "DAILY" => Frequency::Daily,
+                "WEEKLY" => Frequency::Weekly,
+                "MONTHLY" => Frequency::Monthly,
+                "YEARLY" => Frequency::Yearly,
+                _ => return Err(invalid_rule())
+            },
+            "INTERVAL" => rule.interval = part.parse::<u32>().map_err(|_| invalid_rule())?,
+            "COUNT" => rule.count = Some(part.parse::<u32>().map_err(|_| invalid_rule())?),
+            "UNTIL" => {
+                // UNTIL is UTC when it ends in Z, otherwise it's in the same zone as DTSTART.
+                let (until, until_zone, all_day): (NaiveDateTime, EventZone, bool) = parse_ics_time(part, None, false)?;
+                let until: NaiveDateTime = match all_day {
+                    true => until + Duration::days(1) - Duration::seconds(1),
+                    false => until
+                };
+                rule.until = match until_zone {
+                    EventZone::Utc => EventZone::Utc.resolve(until),
+                    _ => zone.resolve(until)
+                };
+            },
+            "BYDAY" => {
+                for day in part.split(',') {
+                    let day: &str = day.trim();
+                    // `get` rather than slicing, the split can land inside a multibyte character.
+                    let split_at: usize = day.len().saturating_sub(2);
+                    let weekday: Weekday = match day.get(split_at..) {
+                        Some("MO") => Weekday::Mon,
+                        Some("TU") => Weekday::Tue,
+                        Some("WE") => Weekday::Wed,
+                        Some("TH") => Weekday::Thu,
+                        Some("FR") => Weekday::Fri,
+                        Some("SA") => Weekday::Sat,
+                        Some("SU") => Weekday::Sun,
+                        _ => return Err(invalid_rule())
+                    };
+                    let position: Option<i32> = match day.get(..split_at) {
+                        Some("") => None,
+                        Some(x) => Some(x.trim_start_matches('+').parse::<i32>().map_err(|_| invalid_rule())?),
+                        None => return Err(invalid_rule())
+                    };
+                    rule.by_day.push((position, weekday));
+                }
+            },
+            "BYMONTHDAY" => {
+                for day in part.split(',') {
+                    rule.by_month_day.push(day.trim().parse::<i32>().map_err(|_| invalid_rule())?);
+                }
+            },
+            "BYMONTH" => {
+                for month in part.split(',') {
+                    rule.by_month.push(month.trim().parse::<u32>().map_err(|_| invalid_rule())?);
+                }
+            },
+            // Week start only matters for rules we don't expand, like weekly BYSETPOS.
+            "WKST" => (),
+            _ => return Err(invalid_rule())
+        }
+    }
+
+    Ok(rule)
+}
+
+fn unescape_text(value: &str) -> String {
+    value.replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::America::New_York;
+
+    const RECURRING: &str = include_str!("../../tests/fixtures/calendar_recurring.ics");
+    const DAYLIGHT_SAVING: &str = include_str!("../../tests/fixtures/calendar_dst.ics");
+    const OUT_OF_RANGE: &str = include_str!("../../tests/fixtures/calendar_out_of_range.ics");
+
+    /// Midnight at the start of a day in New York, on the mirror's clock.
+    fn new_york_midnight(year: i32, month: u32, day: u32) -> DateTime<Local> {
+        New_York.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap().with_timezone(&Local)
+    }
+
+    /// Timed occurrences as New York wall clock times, so the results don't depend on where the tests run.
+    fn new_york_times(occurrences: &[EventOccurrence]) -> Vec<(String, String)> {
+        occurrences.iter()
+            .filter(|occurrence| !occurrence.all_day)
+            .map(|occurrence| (occurrence.start.with_timezone(&New_York).format("%a %F %R").to_string(), occurrence.summary.clone()))
+            .collect()
+    }
+
+    fn utc_times(occurrences: &[EventOccurrence], summary: &str) -> Vec<(String, String)> {
+        occurrences.iter()
+            .filter(|occurrence| occurrence.summary == summary)
+            .map(|occurrence| (
+                occurrence.start.with_timezone(&Utc).format("%FT%RZ").to_string(),
+                occurrence.end.with_timezone(&Utc).format("%FT%RZ").to_string()
+            ))
+            .collect()
+    }
+
+    #[test]
+    fn expands_weekly_rules_with_exceptions() {
+        let events: Vec<CalendarEvent> = parse_ics(RECURRING).unwrap();
+        let occurrences: Vec<EventOccurrence> = expand_events(&events, new_york_midnight(2026, 10, 12), new_york_midnight(2026, 10, 24));
+
+        // The 14th is an EXDATE, the 16th was moved to 10:00 and the 19th was cancelled.
+        assert_eq!(new_york_times(&occurrences), [
+            ("Mon 2026-10-12 09:00".to_string(), "Standup".to_string()),
+            ("Thu 2026-10-15 18:00".to_string(), "Pottery course".to_string()),
+            ("Fri 2026-10-16 10:00".to_string(), "Standup (moved)".to_string()),
+            ("Wed 2026-10-21 09:00".to_string(), "Standup".to_string()),
+            ("Thu 2026-10-22 18:00".to_string(), "Pottery course".to_string()),
+            ("Fri 2026-10-23 09:00".to_string(), "Standup".to_string()),
+        ]);
+
+        let holidays: Vec<&EventOccurrence> = occurrences.iter().filter(|occurrence| occurrence.all_day).collect();
+        assert_eq!(holidays.len(), 1);
+        assert_eq!(holidays[0].summary, "Columbus Day");
+        assert_eq!(holidays[0].start.date_naive(), NaiveDate::from_ymd_opt(2026, 10, 12).unwrap());
+        assert_eq!(holidays[0].end - holidays[0].start, Duration::days(1));
+    }
+
+    #[test]
+    fn stops_at_count_and_until() {
+        let events: Vec<CalendarEvent> = parse_ics(RECURRING).unwrap();
+        let occurrences: Vec<EventOccurrence> = expand_events(&events, new_york_midnight(2026, 9, 1), new_york_midnight(2027, 3, 1));
+        let dates_of = |summary: &str| -> Vec<String> {
+            occurrences.iter()
+                .filter(|occurrence| occurrence.summary == summary)
+                .map(|occurrence| occurrence.start.with_timezone(&New_York).format("%F %R").to_string())
+                .collect()
+        };
+
+        // The last Friday of each month, still at 19:00 after the clocks change on November 1st.
+        assert_eq!(dates_of("Book club, last Friday"), ["2026-09-25 19:00", "2026-10-30 19:00", "2026-11-27 19:00", "2026-12-25 19:00"]);
+        // UNTIL is inclusive.
+        assert_eq!(dates_of("Pottery course"), ["2026-10-01 18:00", "2026-10-08 18:00", "2026-10-15 18:00", "2026-10-22 18:00"]);
+    }
+
+    #[test]
+    fn keeps_wall_clock_times_across_daylight_saving() {
+        let events: Vec<CalendarEvent> = parse_ics(DAYLIGHT_SAVING).unwrap();
+
+        let spring: Vec<EventOccurrence> = expand_events(&events, new_york_midnight(2026, 3, 1), new_york_midnight(2026, 3, 15));
+        assert_eq!(utc_times(&spring, "Morning walk"), [
+            ("2026-03-06T12:00Z".to_string(), "2026-03-06T13:00Z".to_string()),
+            ("2026-03-07T12:00Z".to_string(), "2026-03-07T13:00Z".to_string()),
+            ("2026-03-08T11:00Z".to_string(), "2026-03-08T12:00Z".to_string()),
+            ("2026-03-09T11:00Z".to_string(), "2026-03-09T12:00Z".to_string()),
+            ("2026-03-10T11:00Z".to_string(), "2026-03-10T12:00Z".to_string()),
+        ]);
+        // 2:30 doesn't happen on the 8th, that one moves to 3:30.
+        assert_eq!(utc_times(&spring, "Night shift"), [
+            ("2026-03-07T07:30Z".to_string(), "2026-03-07T08:30Z".to_string()),
+            ("2026-03-08T07:30Z".to_string(), "2026-03-08T08:30Z".to_string()),
+            ("2026-03-09T06:30Z".to_string(), "2026-03-09T07:30Z".to_string()),
+        ]);
+
+        // 1:30 happens twice on November 1st, the first one is used.
+        let fall: Vec<EventOccurrence> = expand_events(&events, new_york_midnight(2026, 10, 25), new_york_midnight(2026, 11, 8));
+        assert_eq!(utc_times(&fall, "Late check"), [
+            ("2026-10-31T05:30Z".to_string(), "2026-10-31T06:00Z".to_string()),
+            ("2026-11-01T05:30Z".to_string(), "2026-11-01T06:00Z".to_string()),
+            ("2026-11-02T06:30Z".to_string(), "2026-11-02T07:00Z".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn rejects_unreadable_byday_values() {
+        assert!(parse_rule("FREQ=WEEKLY;BYDAY=MO,ÉO", EventZone::Utc).is_err());
+        assert!(parse_rule("FREQ=MONTHLY;BYDAY=É", EventZone::Utc).is_err());
+        assert!(parse_rule("FREQ=MONTHLY;BYDAY=XFR", EventZone::Utc).is_err());
+
+        let rule: RecurrenceRule = parse_rule("FREQ=MONTHLY;BYDAY=-1FR,+2MO", EventZone::Utc).unwrap();
+        assert_eq!(rule.by_day, [(Some(-1), Weekday::Fri), (Some(2), Weekday::Mon)]);
+    }
+
+    #[test]
+    fn stops_expanding_past_the_end_of_the_calendar() {
+        let events: Vec<CalendarEvent> = parse_ics(OUT_OF_RANGE).unwrap();
+        let occurrences: Vec<EventOccurrence> = expand_events(&events, new_york_midnight(2026, 10, 1), new_york_midnight(2030, 1, 1));
+
+        // Only the first occurrence of each huge INTERVAL fits, and the events lasting longer
+        // than the calendar goes are skipped.
+        let summaries: Vec<&str> = occurrences.iter().map(|occurrence| occurrence.summary.as_str()).collect();
+        assert_eq!(summaries, ["Every few billion days", "Every few billion weeks", "Every few billion months", "Every few billion years", "Lunch"]);
+    }
+
+    #[test]
+    fn rejects_out_of_range_durations() {
+        assert_eq!(parse_duration("PT1H30M").unwrap(), Duration::minutes(90));
+        assert!(matches!(parse_duration("P999999999999D"), Err(FeedError::Parse { .. })));
+        assert!(matches!(parse_duration("P9999999999999999W"), Err(FeedError::Parse { .. })));
+        assert!(matches!(parse_duration("P106000000000DT100000000000H"), Err(FeedError::Parse { .. })));
+    }
+}
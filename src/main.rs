@@ -1,5 +1,7 @@
 
+mod calendar;
 mod misc;
+mod news;
 mod request_weather;
 mod ui;
 #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
@@ -29,10 +31,12 @@ fn main() {
         Err(SettingsError::SerdeError { error_desc }) => panic!("Error processing user settings file.\n {:?}", error_desc)
     };
 
+    let window_size: (u32, u32) = (user_settings.layout.window_width, user_settings.layout.window_height);
+
     match WeatherGui::run(Settings {
         flags: user_settings,
         window: window::Settings {
-        size: (500, 800),
+        size: window_size,
         ..window::Settings::default()
         },
         ..Settings::default()
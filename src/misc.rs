@@ -1,9 +1,25 @@
-pub use self::errors::{RequestError, UIError, SettingsError};
+pub use self::condition::WeatherCondition;
+pub use self::errors::{WeatherError, UIError, SettingsError, FeedError};
+pub use self::language::Language;
+pub use self::layout::{Layout, FontSizes};
+pub use self::moon::{moon_phase_fraction, moon_illumination, moon_phase_name};
 pub use self::structs::{
-    UserSettings, Astronomy, CurrentConditions, CurrentWeather, DailyForecast, ForecastDay, ForecastHour, Weather, get_weather_desc, get_weather_image
+    UserSettings, HourlyDisplay, Location, NewsFeed, WorldClock, CachedWeather, Astronomy, CurrentConditions, CurrentWeather, DailyForecast, ForecastDay, ForecastHour, Weather, WeatherAlert, AlertSeverity, format_measurement,
+    parse_astronomy_time
 };
+pub use self::units::{
+    UnitSystem, UnitOverrides, fahrenheit_to_celsius,
+    degrees_to_compass, compass_to_degrees
+};
+mod condition;
 mod errors;
+mod language;
+mod layout;
+mod moon;
 mod structs;
+#[cfg(test)]
+pub mod test_server;
+mod units;
 
 pub fn get_current_dir() -> std::path::PathBuf {
     let current_dir: std::path::PathBuf = match std::env::current_exe() {
@@ -0,0 +1,204 @@
+use crate::misc::get_current_dir;
+
+/// Every condition the mirror knows how to show. Providers map their own codes onto these,
+/// wttr.in's codes are used as the stored form since they were here first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WeatherCondition {
+    Clear,
+    PartlyCloudy,
+    Cloudy,
+    Overcast,
+    Mist,
+    Fog,
+    FreezingFog,
+    PatchyRainNearby,
+    PatchyLightDrizzle,
+    LightDrizzle,
+    PatchyLightRain,
+    LightRain,
+    LightRainShower,
+    ModerateRainAtTimes,
+    ModerateRain,
+    ModerateOrHeavyRainShower,
+    HeavyRainAtTimes,
+    HeavyRain,
+    TorrentialRainShower,
+    PatchySleetNearby,
+    LightSleet,
+    LightSleetShowers,
+    ModerateOrHeavySleet,
+    ModerateOrHeavySleetShowers,
+    PatchyFreezingDrizzleNearby,
+    FreezingDrizzle,
+    HeavyFreezingDrizzle,
+    LightFreezingRain,
+    ModerateOrHeavyFreezingRain,
+    PatchySnowNearby,
+    PatchyLightSnow,
+    LightSnow,
+    LightSnowShowers,
+    PatchyModerateSnow,
+    ModerateSnow,
+    ModerateOrHeavySnowShowers,
+    PatchyHeavySnow,
+    HeavySnow,
+    BlowingSnow,
+    Blizzard,
+    LightShowersOfHail,
+    Hail,
+    ModerateOrHeavyShowersOfHail,
+    ThunderyOutbreaksNearby,
+    PatchyLightRainWithThunder,
+    PatchyLightSnowWithThunder,
+    ModerateOrHeavyRainWithThunder,
+    ModerateOrHeavySnowWithThunder,
+    #[default]
+    Unknown,
+}
+
+struct ConditionInfo {
+    condition: WeatherCondition,
+    code: &'static str,
+    description: &'static str,
+    night_description: &'static str,
+    day_icon: &'static str,
+    night_icon: &'static str,
+    /// Higher is worse, used to pick what a day is summarized as.
+    severity: u8,
+}
+
+const fn condition_info(
+    condition: WeatherCondition,
+    code: &'static str,
+    description: &'static str,
+    day_icon: &'static str,
+    night_icon: &'static str,
+    severity: u8
+) -> ConditionInfo {
+    ConditionInfo { condition, code, description, night_description: description, day_icon, night_icon, severity }
+}
+
+const CONDITIONS: &[ConditionInfo] = &[
+    ConditionInfo {
+        condition: WeatherCondition::Clear,
+        code: "113",
+        description: "Sunny/Clear",
+        night_description: "Clear",
+        day_icon: "svg/wi-day-sunny.svg",
+        night_icon: "svg/wi-night-clear.svg",
+        severity: 1,
+    },
+    condition_info(WeatherCondition::PartlyCloudy, "116", "Partly Cloudy", "svg/wi-day-cloudy.svg", "svg/wi-night-partly-cloudy.svg", 2),
+    condition_info(WeatherCondition::Cloudy, "119", "Cloudy", "svg/wi-day-cloudy.svg", "svg/wi-night-cloudy.svg", 3),
+    condition_info(WeatherCondition::Overcast, "122", "Overcast", "svg/wi-day-sunny-overcast.svg", "svg/wi-night-cloudy.svg", 4),
+    condition_info(WeatherCondition::Mist, "143", "Mist", "svg/wi-day-haze.svg", "svg/wi-night-fog.svg", 5),
+    condition_info(WeatherCondition::Fog, "248", "Fog", "svg/wi-day-fog.svg", "svg/wi-night-fog.svg", 6),
+    condition_info(WeatherCondition::FreezingFog, "260", "Freezing fog", "svg/wi-day-fog.svg", "svg/wi-night-fog.svg", 7),
+    condition_info(WeatherCondition::PatchyRainNearby, "176", "Patchy rain nearby", "svg/wi-day-sprinkle.svg", "svg/wi-night-rain.svg", 10),
+    condition_info(WeatherCondition::PatchyLightDrizzle, "263", "Patchy light drizzle", "svg/wi-day-sprinkle.svg", "svg/wi-night-rain.svg", 11),
+    condition_info(WeatherCondition::LightDrizzle, "266", "Light drizzle", "svg/wi-day-sprinkle.svg", "svg/wi-night-rain.svg", 12),
+    condition_info(WeatherCondition::PatchyLightRain, "293", "Patchy light rain", "svg/wi-day-sprinkle.svg", "svg/wi-night-rain.svg", 13),
+    condition_info(WeatherCondition::LightRain, "296", "Light rain", "svg/wi-day-rain.svg", "svg/wi-night-rain.svg", 14),
+    condition_info(WeatherCondition::LightRainShower, "353", "Light rain shower", "svg/wi-day-rain.svg", "svg/wi-night-hail.svg", 15),
+    condition_info(WeatherCondition::ModerateRainAtTimes, "299", "Moderate rain at times", "svg/wi-day-rain.svg", "svg/wi-night-rain.svg", 16),
+    condition_info(WeatherCondition::ModerateRain, "302", "Moderate rain", "svg/wi-day-rain.svg", "svg/wi-night-rain.svg", 17),
+    condition_info(WeatherCondition::ModerateOrHeavyRainShower, "356", "Moderate or heavy rain shower", "svg/wi-day-rain.svg", "svg/wi-night-snow-thunderstorm.svg", 18),
+    condition_info(WeatherCondition::HeavyRainAtTimes, "305", "Heavy rain at times", "svg/wi-day-rain.svg", "svg/wi-night-storm-showers.svg", 19),
+    condition_info(WeatherCondition::HeavyRain, "308", "Heavy rain", "svg/wi-day-rain.svg", "svg/wi-night-storm-showers.svg", 20),
+    condition_info(WeatherCondition::TorrentialRainShower, "359", "Torrential rain shower", "svg/wi-day-thunderstorm.svg", "svg/wi-night-thunderstorm.svg", 21),
+    condition_info(WeatherCondition::PatchySleetNearby, "182", "Patchy sleet nearby", "svg/wi-day-sleet.svg", "svg/wi-night-sleet.svg", 30),
+    condition_info(WeatherCondition::LightSleet, "317", "Light sleet", "svg/wi-day-sleet.svg", "svg/wi-night-sleet.svg", 31),
+    condition_info(WeatherCondition::LightSleetShowers, "362", "Light sleet showers", "svg/wi-day-sleet.svg", "svg/wi-night-sleet.svg", 32),
+    condition_info(WeatherCondition::ModerateOrHeavySleet, "320", "Moderate or heavy sleet", "svg/wi-day-sleet.svg", "svg/wi-night-sleet-storm.svg", 33),
+    condition_info(WeatherCondition::ModerateOrHeavySleetShowers, "365", "Moderate or heavy sleet showers", "svg/wi-day-sleet-storm.svg", "svg/wi-night-sleet-storm.svg", 34),
+    condition_info(WeatherCondition::PatchyFreezingDrizzleNearby, "185", "Patchy freezing drizzle nearby", "svg/wi-day-rain-mix.svg", "svg/wi-night-rain-mix.svg", 40),
+    condition_info(WeatherCondition::FreezingDrizzle, "281", "Freezing drizzle", "svg/wi-day-rain-mix.svg", "svg/wi-night-rain-mix.svg", 41),
+    condition_info(WeatherCondition::HeavyFreezingDrizzle, "284", "Heavy freezing drizzle", "svg/wi-day-rain-mix.svg", "svg/wi-night-rain-mix.svg", 42),
+    condition_info(WeatherCondition::LightFreezingRain, "311", "Light freezing rain", "svg/wi-day-rain-mix.svg", "svg/wi-night-rain-mix.svg", 43),
+    condition_info(WeatherCondition::ModerateOrHeavyFreezingRain, "314", "Moderate or Heavy freezing rain", "svg/wi-day-rain-mix.svg", "svg/wi-night-rain-mix.svg", 44),
+    condition_info(WeatherCondition::PatchySnowNearby, "179", "Patchy snow nearby", "svg/wi-day-snow.svg", "svg/wi-night-snow.svg", 50),
+    condition_info(WeatherCondition::PatchyLightSnow, "323", "Patchy light snow", "svg/wi-day-snow.svg", "svg/wi-night-snow.svg", 51),
+    condition_info(WeatherCondition::LightSnow, "326", "Light snow", "svg/wi-day-snow.svg", "svg/wi-night-snow.svg", 52),
+    condition_info(WeatherCondition::LightSnowShowers, "368", "Light snow showers", "svg/wi-day-snow.svg", "svg/wi-night-snow.svg", 53),
+    condition_info(WeatherCondition::PatchyModerateSnow, "329", "Patchy moderate snow", "svg/wi-day-snow.svg", "svg/wi-night-snow.svg", 54),
+    condition_info(WeatherCondition::ModerateSnow, "332", "Moderate snow", "svg/wi-day-snow.svg", "svg/wi-night-snow.svg", 55),
+    condition_info(WeatherCondition::ModerateOrHeavySnowShowers, "371", "Moderate or heavy snow showers", "svg/wi-day-snow-wind.svg", "svg/wi-night-snow.svg", 56),
+    condition_info(WeatherCondition::PatchyHeavySnow, "335", "Patchy heavy snow", "svg/wi-day-snow-wind.svg", "svg/wi-night-snow.svg", 57),
+    condition_info(WeatherCondition::HeavySnow, "338", "Heavy snow", "svg/wi-day-snow-wind.svg", "svg/wi-night-snow.svg", 58),
+    condition_info(WeatherCondition::BlowingSnow, "227", "Blowing snow", "svg/wi-day-snow-wind.svg", "svg/wi-night-snow-wind.svg", 59),
+    condition_info(WeatherCondition::Blizzard, "230", "Blizzard", "svg/wi-day-snow-thunderstorm.svg", "svg/wi-night-snow-wind.svg", 60),
+    condition_info(WeatherCondition::LightShowersOfHail, "374", "Light showers of hail", "svg/wi-day-hail.svg", "svg/wi-night-hail.svg", 65),
+    condition_info(WeatherCondition::Hail, "350", "Hail", "svg/wi-day-hail.svg", "svg/wi-night-hail.svg", 66),
+    condition_info(WeatherCondition::ModerateOrHeavyShowersOfHail, "377", "Moderate or heavy showers of hail", "svg/wi-day-hail.svg", "svg/wi-night-hail.svg", 67),
+    condition_info(WeatherCondition::ThunderyOutbreaksNearby, "200", "Thundery outbreaks nearby", "svg/wi-day-rain-mix.svg", "svg/wi-night-lightning.svg", 70),
+    condition_info(WeatherCondition::PatchyLightRainWithThunder, "386", "Patchy light rain in area with thunder", "svg/wi-day-rain.svg", "svg/wi-night-rain.svg", 71),
+    condition_info(WeatherCondition::PatchyLightSnowWithThunder, "392", "Patchy light snow in area with thunder", "svg/wi-day-snow-thunderstorm.svg", "svg/wi-night-snow.svg", 72),
+    condition_info(WeatherCondition::ModerateOrHeavyRainWithThunder, "389", "Moderate or heavy rain in area with thunder", "svg/wi-day-thunderstorm.svg", "svg/wi-night-thunderstorm.svg", 73),
+    condition_info(WeatherCondition::ModerateOrHeavySnowWithThunder, "395", "Moderate or heavy snow in area with thunder", "svg/wi-day-thunderstorm.svg", "svg/wi-night-snow-thunderstorm.svg", 74),
+    condition_info(WeatherCondition::Unknown, "NA", "Unknown", "svg/wi-na.svg", "svg/wi-na.svg", 0),
+];
+
+impl WeatherCondition {
+    /// Codes that aren't in the table come back as `Unknown` rather than an error.
+    pub fn from_code(weather_code: &str) -> WeatherCondition {
+        match CONDITIONS.iter().find(|x| x.code == weather_code.trim()) {
+            Some(x) => x.condition,
+            None => WeatherCondition::Unknown
+        }
+    }
+
+    fn info(&self) -> &'static ConditionInfo {
+        match CONDITIONS.iter().find(|x| x.condition == *self) {
+            Some(x) => x,
+            None => &CONDITIONS[CONDITIONS.len() - 1]
+        }
+    }
+
+    /// The wttr.in code, which is what gets stored in `weather_code` and the cache.
+    pub fn code(&self) -> &'static str {
+        self.info().code
+    }
+
+    pub fn description(&self, night: bool) -> String {
+        match night {
+            false => self.info().description.to_string(),
+            true => self.info().night_description.to_string()
+        }
+    }
+
+    pub fn image(&self, night: bool) -> String {
+        let weather_image: &str = match night {
+            false => self.info().day_icon,
+            true => self.info().night_icon
+        };
+
+        let current_directory: std::path::PathBuf = get_current_dir();
+        let file_path: std::path::PathBuf = current_directory.join(weather_image);
+        file_path.to_string_lossy().into_owned()
+    }
+
+    pub fn severity(&self) -> u8 {
+        self.info().severity
+    }
+
+    /// Freezing rain, snow, hail and thunder are worth calling out even when they're brief.
+    pub fn is_notable(&self) -> bool {
+        self.severity() >= WeatherCondition::PatchyFreezingDrizzleNearby.severity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn every_icon_is_in_the_svg_folder() {
+        let repo_directory: &Path = Path::new(env!("CARGO_MANIFEST_DIR"));
+        for info in CONDITIONS {
+            for icon in [info.day_icon, info.night_icon] {
+                assert!(repo_directory.join(icon).is_file(), "{:?} uses {}, which doesn't exist", info.condition, icon);
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use reqwest::StatusCode;
 use std::error::Error;
 use std::fmt;
+use std::io;
 
 #[derive(Debug, Clone)]
 pub enum SettingsError {
@@ -11,49 +12,128 @@ pub enum SettingsError {
 #[derive(Debug, Clone)]
 pub enum UIError {
     DataError {msg: String},
-    APIError {msg: String},
+    APIError {error: WeatherError},
 }
 
-impl From<RequestError> for UIError {
-    fn from(error: RequestError) -> UIError {
-        dbg!(&error);
+impl fmt::Display for UIError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UIError::DataError { msg } => write!(f, "{}", msg),
+            UIError::APIError { error } => write!(f, "{}", error)
+        }
+    }
+}
+
+impl From<WeatherError> for UIError {
+    fn from(error: WeatherError) -> UIError {
+        UIError::APIError { error }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum WeatherError {
+    Network {msg: String},
+    HttpStatus {http_code: StatusCode},
+    UnknownLocation {location: String},
+    Parse {msg: String},
+    CacheMiss {msg: String},
+    Io {msg: String},
+}
+
+impl WeatherError {
+    /// What someone standing in front of the mirror can do about it.
+    pub fn advice(&self) -> String {
+        let advice: &str = match self {
+            WeatherError::Network { .. } => "Check that the mirror is connected to the internet.",
+            WeatherError::HttpStatus { http_code } if *http_code == StatusCode::TOO_MANY_REQUESTS => "The provider is limiting requests, try again later or add another provider.",
+            WeatherError::HttpStatus { http_code } if http_code.is_server_error() => "The provider is having problems, try again later or add another provider.",
+            WeatherError::HttpStatus { .. } => "Check the providers and location in settings.json.",
+            WeatherError::UnknownLocation { .. } => "Check the zip_code, city or latitude and longitude in settings.json.",
+            WeatherError::Parse { .. } => "The provider sent something unexpected, try another provider in settings.json.",
+            WeatherError::CacheMiss { .. } => "There's no recent saved forecast to show while the providers are unreachable.",
+            WeatherError::Io { .. } => "Check that the folder with the executable can be written to.",
+        };
+
+        advice.to_string()
+    }
+}
+
+impl fmt::Display for WeatherError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WeatherError::Network { msg } => write!(f, "Couldn't reach the weather provider. {}", msg),
+            WeatherError::HttpStatus { http_code } => write!(f, "The weather provider answered {} {}.", http_code.as_str(), get_http_status_description(*http_code)),
+            WeatherError::UnknownLocation { location } => write!(f, "The weather provider couldn't find {}.", location),
+            WeatherError::Parse { msg } => write!(f, "Couldn't read the forecast. {}", msg),
+            WeatherError::CacheMiss { msg } => write!(f, "No saved forecast. {}", msg),
+            WeatherError::Io { msg } => write!(f, "Couldn't save or load the forecast. {}", msg),
+        }
+    }
+}
+
+impl Error for WeatherError {}
 
-        UIError::APIError { msg: format!("{:?}", error) }
+impl From<reqwest::Error> for WeatherError {
+    fn from(error: reqwest::Error) -> WeatherError {
+        WeatherError::Network { msg: error.to_string() }
     }
 }
 
-impl From<std::boxed::Box<dyn Error>> for UIError {
-    fn from(error: std::boxed::Box<dyn Error>) -> UIError {
-        dbg!(&error);
+impl From<serde_json::Error> for WeatherError {
+    fn from(error: serde_json::Error) -> WeatherError {
+        WeatherError::Parse { msg: error.to_string() }
+    }
+}
+
+impl From<chrono::ParseError> for WeatherError {
+    fn from(error: chrono::ParseError) -> WeatherError {
+        WeatherError::Parse { msg: error.to_string() }
+    }
+}
 
-        UIError::APIError { msg: format!("{:?}", error) }
+impl From<io::Error> for WeatherError {
+    fn from(error: io::Error) -> WeatherError {
+        WeatherError::Io { msg: error.to_string() }
     }
 }
 
+/// Errors from calendars and other feeds read from a file or a url.
 #[derive(Debug, Clone)]
-pub struct RequestError {
-    pub http_code: StatusCode,
-    pub details: String,
+pub enum FeedError {
+    Network {msg: String},
+    HttpStatus {http_code: StatusCode},
+    Parse {msg: String},
+    Io {msg: String},
 }
 
-impl RequestError {
-    pub fn new(status_code: StatusCode) -> RequestError {
-        RequestError {
-            http_code: status_code,
-            details: get_http_status_description(status_code),
+impl fmt::Display for FeedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FeedError::Network { msg } => write!(f, "Couldn't reach the feed. {}", msg),
+            FeedError::HttpStatus { http_code } => write!(f, "The feed answered {} {}.", http_code.as_str(), get_http_status_description(*http_code)),
+            FeedError::Parse { msg } => write!(f, "Couldn't read the feed. {}", msg),
+            FeedError::Io { msg } => write!(f, "Couldn't open the feed. {}", msg),
         }
     }
 }
 
-impl fmt::Display for RequestError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.details)
+impl Error for FeedError {}
+
+impl From<reqwest::Error> for FeedError {
+    fn from(error: reqwest::Error) -> FeedError {
+        FeedError::Network { msg: error.to_string() }
     }
 }
 
-impl Error for RequestError {
-    fn description(&self) -> &str {
-        &self.details
+impl From<chrono::ParseError> for FeedError {
+    fn from(error: chrono::ParseError) -> FeedError {
+        FeedError::Parse { msg: error.to_string() }
+    }
+}
+
+impl From<io::Error> for FeedError {
+    fn from(error: io::Error) -> FeedError {
+        FeedError::Io { msg: error.to_string() }
     }
 }
 
@@ -119,9 +199,8 @@ fn get_http_status_description(status_code: StatusCode) -> String {
         StatusCode::LOOP_DETECTED => "Loop Detected",
         StatusCode::NOT_EXTENDED => "Not Extended",
         StatusCode::NETWORK_AUTHENTICATION_REQUIRED => "Network Authentication Required",
-        _ => {
-            panic!("Invalid Status Code {}", status_code.as_str())
-        }
+        // Proxies and captive portals send codes that aren't listed above.
+        _ => status_code.canonical_reason().unwrap_or("Unknown Status")
     };
     status_description.to_string()
 }
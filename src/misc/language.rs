@@ -0,0 +1,204 @@
+use chrono::{Locale, NaiveDate, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Display language for labels, condition descriptions and dates.
+/// Weather data is kept in English and translated when the screen is built.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    #[serde(alias = "en")]
+    English,
+    #[serde(alias = "es")]
+    Spanish,
+    #[serde(alias = "de")]
+    German,
+}
+
+impl Language {
+    pub fn locale(&self) -> Locale {
+        match self {
+            Language::English => Locale::en_US,
+            Language::Spanish => Locale::es_ES,
+            Language::German => Locale::de_DE
+        }
+    }
+
+    /// Looks `english` up in the label and condition tables, anything missing is shown as is.
+    pub fn translate(&self, english: &str) -> String {
+        let translation: Option<&(&str, &str, &str)> = LABELS.iter()
+            .chain(CONDITION_DESCRIPTIONS.iter())
+            .find(|(key, _, _)| *key == english);
+
+        match (self, translation) {
+            (Language::Spanish, Some((_, spanish, _))) => spanish.to_string(),
+            (Language::German, Some((_, _, german))) => german.to_string(),
+            _ => english.to_string()
+        }
+    }
+
+    pub fn long_date_format(&self) -> &'static str {
+        match self {
+            Language::English => "%A %B %e, %Y",
+            Language::Spanish => "%A %e de %B de %Y",
+            Language::German => "%A, %e. %B %Y"
+        }
+    }
+
+    /// Day and month names come from chrono's locale data.
+    pub fn format_date(&self, date: NaiveDate, date_format: &str) -> String {
+        Utc.from_utc_datetime(&date.and_time(NaiveTime::MIN))
+            .format_localized(date_format, self.locale())
+            .to_string()
+    }
+}
+
+/// English, Spanish, German.
+const LABELS: &[(&str, &str, &str)] = &[
+    ("Feels like", "Sensación", "Gefühlt"),
+    ("High", "Máx", "Höchst"),
+    ("Low", "Mín", "Tiefst"),
+    ("Humidity", "Humedad", "Luftfeuchtigkeit"),
+    ("Pressure", "Presión", "Luftdruck"),
+    ("Wind", "Viento", "Wind"),
+    ("gusts", "ráfagas", "Böen"),
+    ("Visibility", "Visibilidad", "Sichtweite"),
+    ("UV Index", "Índice UV", "UV-Index"),
+    ("Time", "Hora", "Uhrzeit"),
+    ("chance of precipitation", "probabilidad de precipitación", "Niederschlagsrisiko"),
+    ("chance", "prob.", "Risiko"),
+    ("Precipitation", "Precipitación", "Niederschlag"),
+    ("Sunrise", "Amanecer", "Sonnenaufgang"),
+    ("Sunset", "Atardecer", "Sonnenuntergang"),
+    ("Temperature", "Temperatura", "Temperatur"),
+    ("Precip %", "Precip. %", "Niederschl. %"),
+    ("Until", "Hasta", "Bis"),
+    ("From", "Desde", "Ab"),
+    ("until", "hasta", "bis"),
+    ("Retrying at", "Reintentando a las", "Neuer Versuch um"),
+    ("Couldn't update", "No se pudo actualizar", "Aktualisierung fehlgeschlagen"),
+    ("Updating Weather...", "Actualizando el tiempo...", "Wetter wird aktualisiert..."),
+    ("Whoops! Something went wrong...", "¡Ups! Algo salió mal...", "Hoppla! Etwas ist schiefgelaufen..."),
+    ("Try again", "Reintentar", "Erneut versuchen"),
+    ("Data is less than an hour old", "Datos de hace menos de una hora", "Daten sind weniger als eine Stunde alt"),
+    ("Data is 1 hour old", "Datos de hace 1 hora", "Daten sind 1 Stunde alt"),
+    ("Data is {} hours old", "Datos de hace {} horas", "Daten sind {} Stunden alt"),
+    ("New Moon", "Luna nueva", "Neumond"),
+    ("Waxing Crescent", "Luna creciente", "Zunehmende Sichel"),
+    ("First Quarter", "Cuarto creciente", "Erstes Viertel"),
+    ("Waxing Gibbous", "Gibosa creciente", "Zunehmender Mond"),
+    ("Full Moon", "Luna llena", "Vollmond"),
+    ("Waning Gibbous", "Gibosa menguante", "Abnehmender Mond"),
+    ("Last Quarter", "Cuarto menguante", "Letztes Viertel"),
+    ("Waning Crescent", "Luna menguante", "Abnehmende Sichel"),
+    ("All day", "Todo el día", "Ganztägig"),
+    ("No upcoming events", "No hay eventos próximos", "Keine anstehenden Termine"),
+    ("Updating Calendar...", "Actualizando el calendario...", "Kalender wird aktualisiert..."),
+    ("Updating News...", "Actualizando noticias...", "Nachrichten werden aktualisiert..."),
+    ("No headlines", "No hay titulares", "Keine Schlagzeilen"),
+    ("just now", "ahora mismo", "gerade eben"),
+    ("1 minute ago", "hace 1 minuto", "vor 1 Minute"),
+    ("{} minutes ago", "hace {} minutos", "vor {} Minuten"),
+    ("1 hour ago", "hace 1 hora", "vor 1 Stunde"),
+    ("{} hours ago", "hace {} horas", "vor {} Stunden"),
+    ("1 day ago", "hace 1 día", "vor 1 Tag"),
+    ("{} days ago", "hace {} días", "vor {} Tagen"),
+];
+
+/// Keyed by the English descriptions in the condition table.
+const CONDITION_DESCRIPTIONS: &[(&str, &str, &str)] = &[
+    ("Sunny/Clear", "Soleado/Despejado", "Sonnig/Klar"),
+    ("Clear", "Despejado", "Klar"),
+    ("Partly Cloudy", "Parcialmente nublado", "Teilweise bewölkt"),
+    ("Cloudy", "Nublado", "Bewölkt"),
+    ("Overcast", "Cubierto", "Bedeckt"),
+    ("Mist", "Neblina", "Dunst"),
+    ("Fog", "Niebla", "Nebel"),
+    ("Freezing fog", "Niebla helada", "Gefrierender Nebel"),
+    ("Patchy rain nearby", "Lluvia dispersa en los alrededores", "Vereinzelt Regen in der Nähe"),
+    ("Patchy light drizzle", "Llovizna ligera dispersa", "Vereinzelt leichter Nieselregen"),
+    ("Light drizzle", "Llovizna ligera", "Leichter Nieselregen"),
+    ("Patchy light rain", "Lluvia ligera dispersa", "Vereinzelt leichter Regen"),
+    ("Light rain", "Lluvia ligera", "Leichter Regen"),
+    ("Light rain shower", "Chubasco ligero", "Leichter Regenschauer"),
+    ("Moderate rain at times", "Lluvia moderada a ratos", "Zeitweise mäßiger Regen"),
+    ("Moderate rain", "Lluvia moderada", "Mäßiger Regen"),
+    ("Moderate or heavy rain shower", "Chubascos moderados o fuertes", "Mäßige oder starke Regenschauer"),
+    ("Heavy rain at times", "Lluvia fuerte a ratos", "Zeitweise starker Regen"),
+    ("Heavy rain", "Lluvia fuerte", "Starker Regen"),
+    ("Torrential rain shower", "Chubasco torrencial", "Sintflutartiger Regenschauer"),
+    ("Patchy sleet nearby", "Aguanieve dispersa en los alrededores", "Vereinzelt Schneeregen in der Nähe"),
+    ("Light sleet", "Aguanieve ligera", "Leichter Schneeregen"),
+    ("Light sleet showers", "Chubascos ligeros de aguanieve", "Leichte Schneeregenschauer"),
+    ("Moderate or heavy sleet", "Aguanieve moderada o fuerte", "Mäßiger oder starker Schneeregen"),
+    ("Moderate or heavy sleet showers", "Chubascos de aguanieve moderados o fuertes", "Mäßige oder starke Schneeregenschauer"),
+    ("Patchy freezing drizzle nearby", "Llovizna helada dispersa en los alrededores", "Vereinzelt gefrierender Nieselregen in der Nähe"),
+    ("Freezing drizzle", "Llovizna helada", "Gefrierender Nieselregen"),
+    ("Heavy freezing drizzle", "Llovizna helada fuerte", "Starker gefrierender Nieselregen"),
+    ("Light freezing rain", "Lluvia helada ligera", "Leichter gefrierender Regen"),
+    ("Moderate or Heavy freezing rain", "Lluvia helada moderada o fuerte", "Mäßiger oder starker gefrierender Regen"),
+    ("Patchy snow nearby", "Nieve dispersa en los alrededores", "Vereinzelt Schnee in der Nähe"),
+    ("Patchy light snow", "Nevadas ligeras dispersas", "Vereinzelt leichter Schneefall"),
+    ("Light snow", "Nevada ligera", "Leichter Schneefall"),
+    ("Light snow showers", "Chubascos de nieve ligeros", "Leichte Schneeschauer"),
+    ("Patchy moderate snow", "Nevadas moderadas dispersas", "Vereinzelt mäßiger Schneefall"),
+    ("Moderate snow", "Nevada moderada", "Mäßiger Schneefall"),
+    ("Moderate or heavy snow showers", "Chubascos de nieve moderados o fuertes", "Mäßige oder starke Schneeschauer"),
+    ("Patchy heavy snow", "Nevadas fuertes dispersas", "Vereinzelt starker Schneefall"),
+    ("Heavy snow", "Nevada fuerte", "Starker Schneefall"),
+    ("Blowing snow", "Ventisca", "Schneetreiben"),
+    ("Blizzard", "Tormenta de nieve", "Schneesturm"),
+    ("Light showers of hail", "Chubascos ligeros de granizo", "Leichte Hagelschauer"),
+    ("Hail", "Granizo", "Hagel"),
+    ("Moderate or heavy showers of hail", "Chubascos de granizo moderados o fuertes", "Mäßige oder starke Hagelschauer"),
+    ("Thundery outbreaks nearby", "Tormentas en los alrededores", "Gewitter in der Nähe"),
+    ("Patchy light rain in area with thunder", "Lluvia ligera dispersa con tormenta", "Vereinzelt leichter Regen mit Gewitter"),
+    ("Moderate or heavy rain in area with thunder", "Lluvia moderada o fuerte con tormenta", "Mäßiger oder starker Regen mit Gewitter"),
+    ("Patchy light snow in area with thunder", "Nieve ligera dispersa con tormenta", "Vereinzelt leichter Schneefall mit Gewitter"),
+    ("Moderate or heavy snow in area with thunder", "Nieve moderada o fuerte con tormenta", "Mäßiger oder starker Schneefall mit Gewitter"),
+    ("Unknown", "Desconocido", "Unbekannt"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translates_labels_and_conditions() {
+        assert_eq!(Language::Spanish.translate("Feels like"), "Sensación");
+        assert_eq!(Language::German.translate("Feels like"), "Gefühlt");
+        assert_eq!(Language::Spanish.translate("Moderate or heavy rain in area with thunder"), "Lluvia moderada o fuerte con tormenta");
+        assert_eq!(Language::German.translate("Waning Gibbous"), "Abnehmender Mond");
+        assert_eq!(Language::English.translate("Feels like"), "Feels like");
+
+        // Anything missing from the tables is shown as is.
+        assert_eq!(Language::German.translate("Tornado Warning"), "Tornado Warning");
+    }
+
+    #[test]
+    fn translation_tables_are_complete() {
+        for (english, spanish, german) in LABELS.iter().chain(CONDITION_DESCRIPTIONS.iter()) {
+            assert!(!spanish.is_empty() && !german.is_empty(), "{english} is missing a translation");
+            // Counts are filled in after translating, every language needs the placeholder.
+            assert_eq!(english.contains("{}"), spanish.contains("{}"), "{spanish}");
+            assert_eq!(english.contains("{}"), german.contains("{}"), "{german}");
+            assert_eq!(LABELS.iter().chain(CONDITION_DESCRIPTIONS.iter()).filter(|(key, _, _)| key == english).count(), 1, "{english} is listed twice");
+        }
+    }
+
+    #[test]
+    fn formats_dates_in_each_language() {
+        let date: NaiveDate = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+
+        assert_eq!(Language::English.format_date(date, Language::English.long_date_format()), "Sunday October 18, 2026");
+        assert_eq!(Language::Spanish.format_date(date, Language::Spanish.long_date_format()), "domingo 18 de octubre de 2026");
+        assert_eq!(Language::German.format_date(date, Language::German.long_date_format()), "Sonntag, 18. Oktober 2026");
+        assert_eq!(Language::German.format_date(date, "%a %e %b"), "So 18 Okt");
+    }
+
+    #[test]
+    fn reads_language_names_and_codes() {
+        let languages: Vec<Language> = serde_json::from_str(r#"["english", "es", "german", "de"]"#).unwrap();
+        assert_eq!(languages, [Language::English, Language::Spanish, Language::German, Language::German]);
+    }
+}
@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+
+/// Where modules go and how big everything is. The defaults match a 500x800 portrait screen.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Layout {
+    /// Modules in each region, top to bottom or left to right.
+    #[serde(default = "default_top_bar")]
+    pub top_bar: Vec<String>,
+    #[serde(default = "default_left")]
+    pub left: Vec<String>,
+    #[serde(default)]
+    pub right: Vec<String>,
+    #[serde(default)]
+    pub bottom: Vec<String>,
+    /// Share of the width given to the left and right regions.
+    #[serde(default = "default_region_portion")]
+    pub left_width: u16,
+    #[serde(default = "default_region_portion")]
+    pub right_width: u16,
+    #[serde(default = "default_window_width")]
+    pub window_width: u32,
+    #[serde(default = "default_window_height")]
+    pub window_height: u32,
+    #[serde(default = "default_spacing")]
+    pub spacing: u16,
+    #[serde(default = "default_padding")]
+    pub padding: u16,
+    /// Space taken up by the lines between sections.
+    #[serde(default = "default_rule_width")]
+    pub rule_width: u16,
+    /// Widest the weather's current and forecast columns will grow.
+    #[serde(default = "default_column_max_width")]
+    pub column_max_width: u16,
+    /// Share of the weather's left column given to current conditions and to the hourly forecast.
+    #[serde(default = "default_current_height")]
+    pub current_height: u16,
+    #[serde(default = "default_hourly_height")]
+    pub hourly_height: u16,
+    #[serde(default)]
+    pub font_sizes: FontSizes,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+pub struct FontSizes {
+    #[serde(default = "default_title_size")]
+    pub title: u16,
+    #[serde(default = "default_body_size")]
+    pub body: u16,
+    #[serde(default = "default_small_size")]
+    pub small: u16,
+}
+
+fn default_top_bar() -> Vec<String> {
+    vec!["clock".to_string()]
+}
+
+fn default_left() -> Vec<String> {
+    vec!["weather".to_string()]
+}
+
+fn default_region_portion() -> u16 {
+    1
+}
+
+fn default_window_width() -> u32 {
+    500
+}
+
+fn default_window_height() -> u32 {
+    800
+}
+
+fn default_spacing() -> u16 {
+    20
+}
+
+fn default_padding() -> u16 {
+    10
+}
+
+fn default_rule_width() -> u16 {
+    25
+}
+
+fn default_column_max_width() -> u16 {
+    500
+}
+
+fn default_current_height() -> u16 {
+    5
+}
+
+fn default_hourly_height() -> u16 {
+    12
+}
+
+fn default_title_size() -> u16 {
+    40
+}
+
+fn default_body_size() -> u16 {
+    30
+}
+
+fn default_small_size() -> u16 {
+    20
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout {
+            top_bar: default_top_bar(),
+            left: default_left(),
+            right: Vec::new(),
+            bottom: Vec::new(),
+            left_width: default_region_portion(),
+            right_width: default_region_portion(),
+            window_width: default_window_width(),
+            window_height: default_window_height(),
+            spacing: default_spacing(),
+            padding: default_padding(),
+            rule_width: default_rule_width(),
+            column_max_width: default_column_max_width(),
+            current_height: default_current_height(),
+            hourly_height: default_hourly_height(),
+            font_sizes: FontSizes::default(),
+        }
+    }
+}
+
+impl Default for FontSizes {
+    fn default() -> Self {
+        FontSizes {
+            title: default_title_size(),
+            body: default_body_size(),
+            small: default_small_size(),
+        }
+    }
+}
+
+impl Layout {
+    pub fn module_names(&self) -> impl Iterator<Item = &String> {
+        self.top_bar.iter()
+            .chain(self.left.iter())
+            .chain(self.right.iter())
+            .chain(self.bottom.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_layouts_keep_the_default_modules() {
+        let layout: Layout = serde_json::from_str(r#"{"right": ["news"], "spacing": 10}"#).unwrap();
+        assert_eq!(layout.top_bar, ["clock"]);
+        assert_eq!(layout.left, ["weather"]);
+        assert_eq!(layout.right, ["news"]);
+
+        // Emptying a region on purpose still works.
+        let layout: Layout = serde_json::from_str(r#"{"top_bar": []}"#).unwrap();
+        assert!(layout.top_bar.is_empty());
+        assert_eq!(layout.left, ["weather"]);
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, TimeZone, Utc};
+
+const SYNODIC_MONTH_DAYS: f64 = 29.530_588_853;
+
+/// How far through the lunar cycle `time` is, 0 and 1 are new moon and 0.5 is full.
+/// Counted from the new moon of January 6th 2000, close enough for drawing the phase.
+pub fn moon_phase_fraction<Tz: TimeZone>(time: &DateTime<Tz>) -> f32 {
+    let reference_new_moon: DateTime<Utc> = match Utc.with_ymd_and_hms(2000, 1, 6, 18, 14, 0) {
+        chrono::LocalResult::Single(x) => x,
+        _ => return 0.0
+    };
+
+    let days_since: f64 = (time.with_timezone(&Utc) - reference_new_moon).num_seconds() as f64 / 86_400.0;
+    (days_since / SYNODIC_MONTH_DAYS).rem_euclid(1.0) as f32
+}
+
+pub fn moon_illumination(phase_fraction: f32) -> f32 {
+    (1.0 - (phase_fraction * std::f32::consts::TAU).cos()) / 2.0 * 100.0
+}
+
+/// Names match the ones wttr.in uses for `moon_phase`.
+pub fn moon_phase_name(phase_fraction: f32) -> &'static str {
+    match (phase_fraction * 8.0).round() as u32 % 8 {
+        0 => "New Moon",
+        1 => "Waxing Crescent",
+        2 => "First Quarter",
+        3 => "Waxing Gibbous",
+        4 => "Full Moon",
+        5 => "Waning Gibbous",
+        6 => "Last Quarter",
+        _ => "Waning Crescent"
+    }
+}
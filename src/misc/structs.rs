@@ -1,20 +1,119 @@
-use chrono::{NaiveTime, ParseError, NaiveDate};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::error::Error;
-use std::fs::{self, remove_file, File, OpenOptions};
-use std::io::{self, Write};
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDateTime, NaiveTime, ParseError, NaiveDate, TimeZone, Timelike};
+use chrono::format::{Item, StrftimeItems};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::fs::{self, OpenOptions};
 use std::path::Path;
 use std::fmt;
 
-use crate::misc::{SettingsError, get_current_dir};
+use crate::misc::{SettingsError, WeatherError, WeatherCondition, Language, Layout, UnitSystem, UnitOverrides};
+use crate::request_weather::is_provider_name;
+use crate::ui::is_module_name;
 
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct UserSettings {
+    #[serde(default)]
     pub zip_code: String,
     pub unit: String,
+    #[serde(default)]
+    pub units: UnitOverrides,
     #[serde(alias = "12_hour")]
     pub hour_12: bool,
+    #[serde(default = "default_providers")]
+    pub providers: Vec<String>,
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
+    #[serde(default = "default_nws_user_agent")]
+    pub nws_user_agent: String,
+    #[serde(default)]
+    pub locations: Vec<Location>,
+    #[serde(default = "default_cache_max_age_hours")]
+    pub cache_max_age_hours: u32,
+    #[serde(default = "default_hourly_slots")]
+    pub hourly_slots: usize,
+    #[serde(default = "default_forecast_days")]
+    pub forecast_days: usize,
+    #[serde(default)]
+    pub hourly_display: HourlyDisplay,
+    #[serde(default = "default_chart_hours")]
+    pub chart_hours: i64,
+    #[serde(default)]
+    pub language: Language,
+    #[serde(default)]
+    pub layout: Layout,
+    /// iCalendar files next to the executable, or http, https and webcal urls.
+    #[serde(default)]
+    pub calendars: Vec<String>,
+    #[serde(default = "default_calendar_events")]
+    pub calendar_events: usize,
+    #[serde(default = "default_calendar_days")]
+    pub calendar_days: i64,
+    #[serde(default)]
+    pub news_feeds: Vec<NewsFeed>,
+    /// Seconds each headline is shown for.
+    #[serde(default = "default_news_interval_secs")]
+    pub news_interval_secs: u64,
+    #[serde(default = "default_news_headlines")]
+    pub news_headlines: usize,
+    /// A strftime format for the clock, replaces `hour_12` and `clock_seconds` when set.
+    #[serde(default)]
+    pub clock_format: String,
+    #[serde(default)]
+    pub clock_seconds: bool,
+    #[serde(default)]
+    pub world_clocks: Vec<WorldClock>,
+}
+
+/// What fills the bottom of the left column.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HourlyDisplay {
+    #[default]
+    Cards,
+    Chart,
+}
+
+fn default_providers() -> Vec<String> {
+    vec!["wttr".to_string()]
+}
+
+fn default_nws_user_agent() -> String {
+    "magic_mirror".to_string()
+}
+
+fn default_cache_max_age_hours() -> u32 {
+    12
+}
+
+fn default_hourly_slots() -> usize {
+    8
+}
+
+fn default_forecast_days() -> usize {
+    3
+}
+
+fn default_chart_hours() -> i64 {
+    24
+}
+
+fn default_calendar_events() -> usize {
+    10
+}
+
+fn default_calendar_days() -> i64 {
+    30
+}
+
+fn default_news_interval_secs() -> u64 {
+    10
+}
+
+fn default_news_headlines() -> usize {
+    20
 }
 
 impl UserSettings {
@@ -23,7 +122,29 @@ impl UserSettings {
             let temp_settings = UserSettings {
                 zip_code: "".to_string(),
                 unit: "F".to_string(),
+                units: UnitOverrides::default(),
                 hour_12: true,
+                providers: default_providers(),
+                latitude: None,
+                longitude: None,
+                nws_user_agent: default_nws_user_agent(),
+                locations: Vec::new(),
+                cache_max_age_hours: default_cache_max_age_hours(),
+                hourly_slots: default_hourly_slots(),
+                forecast_days: default_forecast_days(),
+                hourly_display: HourlyDisplay::default(),
+                chart_hours: default_chart_hours(),
+                language: Language::default(),
+                layout: Layout::default(),
+                calendars: Vec::new(),
+                calendar_events: default_calendar_events(),
+                calendar_days: default_calendar_days(),
+                news_feeds: Vec::new(),
+                news_interval_secs: default_news_interval_secs(),
+                news_headlines: default_news_headlines(),
+                clock_format: "".to_string(),
+                clock_seconds: false,
+                world_clocks: Vec::new(),
             };
 
             let settings_json = match OpenOptions::new()
@@ -55,72 +176,225 @@ impl UserSettings {
             }
         };
 
-        if user_settings.unit.to_lowercase() != "f" && user_settings.unit.to_lowercase() != "c" {
+        if UnitSystem::from_name(&user_settings.unit).is_none() {
             return Err(SettingsError::SerdeError { error_desc: "Invalid unit.".to_string() });
         }
 
+        // Open-Meteo has the longest forecast at 16 days.
+        if !(1..=16).contains(&user_settings.forecast_days) {
+            return Err(SettingsError::SerdeError { error_desc: "forecast_days must be between 1 and 16.".to_string() });
+        }
+
+        if !(24..=48).contains(&user_settings.chart_hours) {
+            return Err(SettingsError::SerdeError { error_desc: "chart_hours must be between 24 and 48.".to_string() });
+        }
+
+        if user_settings.calendar_days < 1 {
+            return Err(SettingsError::SerdeError { error_desc: "calendar_days must be at least 1.".to_string() });
+        }
+
+        if user_settings.news_interval_secs < 1 {
+            return Err(SettingsError::SerdeError { error_desc: "news_interval_secs must be at least 1.".to_string() });
+        }
+
+        for news_feed in &user_settings.news_feeds {
+            if !news_feed.url.starts_with("http://") && !news_feed.url.starts_with("https://") {
+                return Err(SettingsError::SerdeError { error_desc: format!("News feed {} needs an http or https url.", news_feed.name) });
+            }
+        }
+
+        if StrftimeItems::new(&user_settings.clock_format).any(|item| item == Item::Error) {
+            return Err(SettingsError::SerdeError { error_desc: format!("Invalid clock_format: {}.", user_settings.clock_format) });
+        }
+
+        for world_clock in &user_settings.world_clocks {
+            if world_clock.time_zone.parse::<Tz>().is_err() {
+                return Err(SettingsError::SerdeError {
+                    error_desc: format!("Unknown time zone {} for world clock {}, use a name like Asia/Tokyo.", world_clock.time_zone, world_clock.name)
+                });
+            }
+        }
+
+        for provider_name in &user_settings.providers {
+            if !is_provider_name(provider_name) {
+                return Err(SettingsError::SerdeError { error_desc: format!("Invalid weather provider: {provider_name}.") });
+            }
+        }
+
+        for module_name in user_settings.layout.module_names() {
+            if !is_module_name(module_name) {
+                return Err(SettingsError::SerdeError { error_desc: format!("Invalid module in layout: {module_name}.") });
+            }
+        }
+
+        for location in &user_settings.locations {
+            if location.query().is_empty() {
+                return Err(SettingsError::SerdeError {
+                    error_desc: format!("Location {} needs a zip_code, city or latitude and longitude.", location.name)
+                });
+            }
+        }
+
         Ok(user_settings)
     }
+
+    pub fn unit_system(&self) -> UnitSystem {
+        UnitSystem::from_name(&self.unit).unwrap_or_default().with_overrides(&self.units)
+    }
+
+    /// The first location is shown in full, the rest get a compact card.
+    /// Older settings files only have the top level zip_code, latitude and longitude.
+    pub fn all_locations(&self) -> Vec<Location> {
+        if !self.locations.is_empty() {
+            return self.locations.clone();
+        }
+
+        vec![Location {
+            name: "".to_string(),
+            zip_code: self.zip_code.clone(),
+            city: "".to_string(),
+            latitude: self.latitude,
+            longitude: self.longitude,
+        }]
+    }
+}
+
+/// An RSS or Atom feed for the news ticker. Without a name the feed's own title is shown.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct NewsFeed {
+    #[serde(default)]
+    pub name: String,
+    pub url: String,
+}
+
+/// Another time zone shown under the clock, `time_zone` is an IANA name like `Asia/Tokyo`.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct WorldClock {
+    pub name: String,
+    pub time_zone: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Location {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, alias = "zip")]
+    pub zip_code: String,
+    #[serde(default)]
+    pub city: String,
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
+}
+
+impl Location {
+    /// Free-form location for providers that accept a search string, like wttr.in.
+    pub fn query(&self) -> String {
+        if !self.zip_code.is_empty() {
+            return self.zip_code.clone();
+        }
+        if !self.city.is_empty() {
+            return self.city.clone();
+        }
+
+        match (self.latitude, self.longitude) {
+            (Some(latitude), Some(longitude)) => format!("{latitude},{longitude}"),
+            _ => "".to_string()
+        }
+    }
+
+    /// Named after a hash of `query()`, so locations with the same name or no name at all each get their own file.
+    pub fn cache_file_name(&self) -> String {
+        // FNV-1a, std's hasher isn't guaranteed to give the same value from one release to the next.
+        let query_hash: u64 = self.query().bytes().fold(0xcbf2_9ce4_8422_2325, |hash: u64, byte: u8| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+
+        format!("last_weather_{query_hash:016x}.json")
+    }
+}
+
+/// Saved after every successful fetch so the forecast can be rebuilt when the providers can't be reached.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CachedWeather {
+    pub fetched_at: DateTime<Local>,
+    pub provider: String,
+    pub location: Location,
+    pub payload: String,
+}
+
+impl CachedWeather {
+    pub fn is_stale(&self, max_age_hours: u32) -> bool {
+        Local::now() - self.fetched_at > Duration::hours(max_age_hours as i64)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Weather {
-    pub last_time_updated: String,
-    pub last_date_updated: String,
     pub daily_forecast: DailyForecast,
     pub current_weather: CurrentWeather,
+    pub alerts: Vec<WeatherAlert>,
+    /// When the forecast came from the cache, the time it was originally fetched.
+    pub cached_at: Option<DateTime<Local>>,
+    /// The forecast location's offset from UTC, `None` when the provider doesn't say.
+    pub utc_offset: Option<FixedOffset>,
 }
 
 impl Weather {
-    pub fn new(weather_json: String, weather_time: String, weather_date: String, hour_12: bool) -> Result<Weather, Box<dyn Error>> {
-        let mut temp_current: CurrentWeather = serde_json::from_str(weather_json.as_str())?;
-        let mut temp_daily: DailyForecast = serde_json::from_str(weather_json.as_str())?;
-        let sunset: String = temp_daily.weather[0].astronomy[0].sunset.clone();
-
-        temp_current.current_condition[0].weather_desc = get_weather_desc(
-            &temp_current.current_condition[0].weather_code,
-            &weather_time,
-            &sunset,
-        );
-        temp_current.current_condition[0].weather_image = get_weather_image(
-            &temp_current.current_condition[0].weather_code,
-            &weather_time,
-            &sunset,
-            false
-        );
+    pub fn new(
+        mut temp_current: CurrentWeather,
+        mut temp_daily: DailyForecast,
+        weather_time: String,
+        weather_date: String,
+        hour_12: bool,
+        utc_offset: Option<FixedOffset>
+    ) -> Result<Weather, WeatherError> {
+        // The update time is on the mirror's clock, the sun times are on the location's.
+        let updated_at: Option<DateTime<Local>> = NaiveDateTime::parse_from_str(&format!("{} {}", weather_date, weather_time), "%D %I:%M %P")
+            .ok()
+            .and_then(|x| Local.from_local_datetime(&x).single());
+        let location_time: NaiveTime = match (updated_at, utc_offset) {
+            (Some(x), Some(offset)) => x.with_timezone(&offset).time(),
+            (Some(x), None) => x.time(),
+            (None, _) => Local::now().time()
+        };
+
+        let today_astronomy: &Astronomy = match temp_daily.weather.first().and_then(|day| day.astronomy.first()) {
+            Some(x) => x,
+            None => return Err(WeatherError::Parse { msg: "Forecast has no sunrise or sunset for today.".to_string() })
+        };
+        let current_night: bool = is_night(location_time, &today_astronomy.sunrise, &today_astronomy.sunset);
+        let current_conditions: &mut CurrentConditions = match temp_current.current_condition.first_mut() {
+            Some(x) => x,
+            None => return Err(WeatherError::Parse { msg: "Forecast has no current conditions.".to_string() })
+        };
+        let current_condition: WeatherCondition = WeatherCondition::from_code(&current_conditions.weather_code);
+        current_conditions.weather_desc = current_condition.description(current_night);
+        current_conditions.weather_image = current_condition.image(current_night);
         
         let mut weather_days: Vec<ForecastDay> = Vec::new();
         for mut day in temp_daily.weather {
             day.date = reformat_date(day.raw_date.clone())?;
-            day.weather_code = get_daily_weather_code(day.hourly.clone());
-            day.weather_desc = get_weather_desc(
-                &day.weather_code,
-                &weather_time,
-                &day.astronomy[0].sunset
-            );
-            day.weather_image = get_weather_image(
-                &day.weather_code,
-                &weather_time,
-                &day.astronomy[0].sunset,
-                true
-            );
+            let astronomy: Astronomy = match day.astronomy.first() {
+                Some(x) => x.clone(),
+                None => return Err(WeatherError::Parse { msg: format!("Forecast has no sunrise or sunset for {}.", day.raw_date) })
+            };
+            day.weather_code = get_daily_weather_code(&day.hourly, &astronomy.sunrise, &astronomy.sunset);
+            // Daily cards always use the daytime description and icon.
+            let day_condition: WeatherCondition = WeatherCondition::from_code(&day.weather_code);
+            day.weather_desc = day_condition.description(false);
+            day.weather_image = day_condition.image(false);
 
             let mut weather_hour: Vec<ForecastHour> = Vec::new();
             for mut hour in day.hourly {
                 let (temp_time, hour_time): (String, NaiveTime) = reformat_time(hour.raw_time.clone(), hour_12);
                 hour.time = temp_time;
 
-                hour.weather_desc = get_weather_desc(
-                    &hour.weather_code,
-                    &hour_time.format("%I:%M %P").to_string(),
-                    &day.astronomy[0].sunset.clone()
-                );
-                hour.weather_image = get_weather_image(
-                    &hour.weather_code,
-                    &hour_time.format("%I:%M %P").to_string(),
-                    &day.astronomy[0].sunset,
-                    false
-                );
+                let hour_night: bool = is_night(hour_time, &astronomy.sunrise, &astronomy.sunset);
+                let hour_condition: WeatherCondition = WeatherCondition::from_code(&hour.weather_code);
+                hour.weather_desc = hour_condition.description(hour_night);
+                hour.weather_image = hour_condition.image(hour_night);
                 
                 weather_hour.push(hour.clone());
             };
@@ -132,43 +406,133 @@ impl Weather {
         temp_daily.weather = weather_days;
 
         let weather_forecast: Weather = Weather {
-            last_time_updated: weather_time,
-            last_date_updated: weather_date,
             current_weather: temp_current,
             daily_forecast: temp_daily,
+            alerts: Vec::new(),
+            cached_at: None,
+            utc_offset,
         };
 
-        save_weather_json(weather_json)?;
-
         Ok(weather_forecast)
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AlertSeverity {
+    Unknown,
+    Minor,
+    Moderate,
+    Severe,
+    Extreme,
+}
+
+impl AlertSeverity {
+    pub fn from_name(severity: &str) -> AlertSeverity {
+        match severity.to_lowercase().as_str() {
+            "minor" => AlertSeverity::Minor,
+            "moderate" => AlertSeverity::Moderate,
+            "severe" => AlertSeverity::Severe,
+            "extreme" => AlertSeverity::Extreme,
+            _ => AlertSeverity::Unknown
+        }
+    }
+}
+
+/// Longest alert description shown on the banner.
+const ALERT_SUMMARY_CHARS: usize = 160;
+
+#[derive(Debug, Clone)]
+pub struct WeatherAlert {
+    pub headline: String,
+    pub severity: AlertSeverity,
+    pub effective: DateTime<Local>,
+    pub expires: DateTime<Local>,
+    pub description: String,
+}
+
+impl WeatherAlert {
+    pub fn is_expired(&self) -> bool {
+        self.expires <= Local::now()
+    }
+
+    /// Alerts can be issued ahead of time, like an advisory that starts the next morning.
+    pub fn is_upcoming(&self) -> bool {
+        self.effective > Local::now()
+    }
+
+    /// The description's first paragraph on one line, cut short enough for the banner.
+    pub fn summary(&self) -> String {
+        let first_paragraph: String = self.description.split("\n\n")
+            .find(|paragraph| !paragraph.trim().is_empty())
+            .unwrap_or("")
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        if first_paragraph.chars().count() <= ALERT_SUMMARY_CHARS {
+            return first_paragraph;
+        }
+
+        let shortened: String = first_paragraph.chars().take(ALERT_SUMMARY_CHARS).collect();
+        match shortened.rfind(' ') {
+            Some(x) => format!("{}...", &shortened[..x]),
+            None => format!("{}...", shortened)
+        }
+    }
+
+    /// Severe and extreme alerts are the warnings, everything below is a watch or advisory.
+    pub fn is_warning(&self) -> bool {
+        self.severity >= AlertSeverity::Severe
+    }
+}
+
+/// Measurements are stored in metric, °C, km/h, hPa, km and mm. `UnitSystem` converts them for display.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DailyForecast {
     pub weather: Vec<ForecastDay>,
 }
 
+impl DailyForecast {
+    /// The hour that's underway at `now` followed by every later one, running on into the next days.
+    pub fn upcoming_hours(&self, now: NaiveDateTime) -> Vec<(NaiveDateTime, &ForecastHour)> {
+        let mut timed_hours: Vec<(NaiveDateTime, &ForecastHour)> = Vec::new();
+        for day in &self.weather {
+            let day_date: NaiveDate = match NaiveDate::parse_from_str(day.raw_date.as_str(), "%F") {
+                Ok(x) => x,
+                Err(_) => continue
+            };
+
+            for hour in &day.hourly {
+                if let Some(start_time) = hour.start_time() {
+                    timed_hours.push((day_date.and_time(start_time), hour));
+                }
+            }
+        }
+
+        let current_index: usize = timed_hours.iter()
+            .position(|(start, _)| *start > now)
+            .unwrap_or(timed_hours.len())
+            .saturating_sub(1);
+
+        timed_hours.into_iter().skip(current_index).collect()
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ForecastDay {
     #[serde(alias = "date")]
-    raw_date: String,
+    pub raw_date: String,
     #[serde(skip_deserializing)]
     pub date: String,
-    #[serde(alias = "avgtempF")]
-    pub avg_temp_f: String,
-    #[serde(alias = "maxtempF")]
-    pub max_temp_f: String,
-    #[serde(alias = "mintempF")]
-    pub min_temp_f: String,
-    #[serde(alias = "avgtempC")]
-    pub avg_temp_c: String,
-    #[serde(alias = "maxtempC")]
-    pub max_temp_c: String,
-    #[serde(alias = "mintempC")]
-    pub min_temp_c: String,
-    #[serde(alias = "uvIndex")]
-    pub uv_index: String,
+    #[serde(alias = "avgtempC", default, deserialize_with = "deserialize_measurement")]
+    pub avg_temp: Option<f32>,
+    #[serde(alias = "maxtempC", default, deserialize_with = "deserialize_measurement")]
+    pub max_temp: Option<f32>,
+    #[serde(alias = "mintempC", default, deserialize_with = "deserialize_measurement")]
+    pub min_temp: Option<f32>,
+    #[serde(alias = "uvIndex", default, deserialize_with = "deserialize_measurement")]
+    pub uv_index: Option<f32>,
+    #[serde(skip_deserializing)]
+    pub total_precip: Option<f32>,
     #[serde(skip_deserializing)]
     pub weather_code: String,
     #[serde(skip_deserializing)]
@@ -188,6 +552,12 @@ impl fmt::Display for ForecastDay {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Astronomy {
     pub moon_phase: String,
+    #[serde(default, deserialize_with = "deserialize_measurement")]
+    pub moon_illumination: Option<f32>,
+    #[serde(default)]
+    pub moonrise: String,
+    #[serde(default)]
+    pub moonset: String,
     pub sunrise: String,
     pub sunset: String,
 }
@@ -195,21 +565,27 @@ pub struct Astronomy {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ForecastHour {
     #[serde(alias = "time")]
-    raw_time: String,
+    pub raw_time: String,
     #[serde(skip_deserializing)]
     pub time: String,
-    #[serde(alias = "tempF")]
-    pub temp_f: String,
-    #[serde(alias = "tempC")]
-    pub temp_c: String,
-    #[serde(alias = "FeelsLikeF")]
-    pub feels_like_f: String,
-    #[serde(alias = "FeelsLikeC")]
-    pub feels_like_c: String,
-    #[serde(alias = "chanceofrain")]
-    pub chance_of_rain: String,
-    #[serde(alias = "chanceofsnow")]
-    pub chance_of_snow: String,
+    #[serde(alias = "tempC", default, deserialize_with = "deserialize_measurement")]
+    pub temp: Option<f32>,
+    #[serde(alias = "FeelsLikeC", default, deserialize_with = "deserialize_measurement")]
+    pub feels_like: Option<f32>,
+    #[serde(alias = "chanceofrain", default, deserialize_with = "deserialize_measurement")]
+    pub chance_of_rain: Option<f32>,
+    #[serde(alias = "chanceofsnow", default, deserialize_with = "deserialize_measurement")]
+    pub chance_of_snow: Option<f32>,
+    #[serde(alias = "precipMM", default, deserialize_with = "deserialize_measurement")]
+    pub precip: Option<f32>,
+    #[serde(alias = "windspeedKmph", default, deserialize_with = "deserialize_measurement")]
+    pub wind_speed: Option<f32>,
+    #[serde(alias = "WindGustKmph", default, deserialize_with = "deserialize_measurement")]
+    pub wind_gust: Option<f32>,
+    #[serde(alias = "winddirDegree", default, deserialize_with = "deserialize_measurement")]
+    pub wind_dir_degree: Option<f32>,
+    #[serde(alias = "winddir16Point", default)]
+    pub wind_dir: String,
     #[serde(alias = "weatherCode")]
     pub weather_code: String,
     #[serde(skip_deserializing)]
@@ -218,6 +594,20 @@ pub struct ForecastHour {
     pub weather_image: String,
 }
 
+impl ForecastHour {
+    pub fn start_time(&self) -> Option<NaiveTime> {
+        parse_raw_time(&self.raw_time)
+    }
+
+    /// Combined chance of rain and snow, `None` when neither was reported.
+    pub fn chance_of_precip(&self) -> Option<f32> {
+        match (self.chance_of_rain, self.chance_of_snow) {
+            (None, None) => None,
+            (rain_chance, snow_chance) => Some(rain_chance.unwrap_or(0.0) + snow_chance.unwrap_or(0.0))
+        }
+    }
+}
+
 impl fmt::Display for ForecastHour {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "ForecastHour({})", self.time)
@@ -233,248 +623,145 @@ pub struct CurrentWeather {
 pub struct CurrentConditions {
     #[serde(alias = "weatherCode")]
     pub weather_code: String,
-    #[serde(alias = "FeelsLikeF")]
-    pub feels_like_f: String,
-    #[serde(alias = "FeelsLikeC")]
-    pub feels_like_c: String,
-    #[serde(alias = "temp_C")]
-    pub temp_c: String,
-    #[serde(alias = "temp_F")]
-    pub temp_f: String,
-    pub humidity: String,
-    pub pressure: String,
-    #[serde(alias = "uvIndex")]
-    pub uv_index: String,
-    pub visibility: String,
+    #[serde(alias = "FeelsLikeC", default, deserialize_with = "deserialize_measurement")]
+    pub feels_like: Option<f32>,
+    #[serde(alias = "temp_C", default, deserialize_with = "deserialize_measurement")]
+    pub temp: Option<f32>,
+    #[serde(default, deserialize_with = "deserialize_measurement")]
+    pub humidity: Option<f32>,
+    #[serde(default, deserialize_with = "deserialize_measurement")]
+    pub pressure: Option<f32>,
+    #[serde(alias = "uvIndex", default, deserialize_with = "deserialize_measurement")]
+    pub uv_index: Option<f32>,
+    #[serde(default, deserialize_with = "deserialize_measurement")]
+    pub visibility: Option<f32>,
+    #[serde(alias = "precipMM", default, deserialize_with = "deserialize_measurement")]
+    pub precip: Option<f32>,
+    #[serde(alias = "windspeedKmph", default, deserialize_with = "deserialize_measurement")]
+    pub wind_speed: Option<f32>,
+    #[serde(alias = "WindGustKmph", default, deserialize_with = "deserialize_measurement")]
+    pub wind_gust: Option<f32>,
+    #[serde(alias = "winddirDegree", default, deserialize_with = "deserialize_measurement")]
+    pub wind_dir_degree: Option<f32>,
+    #[serde(alias = "winddir16Point", default)]
+    pub wind_dir: String,
     #[serde(skip_deserializing)]
     pub weather_desc: String,
     #[serde(skip_deserializing)]
     pub weather_image: String,
 }
 
-pub fn get_weather_desc(weather_code: &str, last_time_updated: &str, sunset: &str) -> String {
-    let weather_desc: &str = match weather_code {
-        "113" => {
-            match is_night(last_time_updated, sunset, false) {
-                false => "Sunny/Clear",
-                true => "Clear"
-            }
-        },
-        "116" => "Partly Cloudy",
-        "119" => "Cloudy",
-        "122" => "Overcast",
-        "143" => "Mist",
-        "176" => "Patchy rain nearby",
-        "179" => "Patchy snow nearby",
-        "182" => "Patchy sleet nearby",
-        "185" => "Patchy freezing drizzle nearby",
-        "200" => "Thundery outbreaks nearby",
-        "227" => "Blowing snow",
-        "230" => "Blizzard",
-        "248" => "Fog",
-        "260" => "Freezing fog",
-        "263" => "Patchy light drizzle",
-        "266" => "Light drizzle",
-        "281" => "Freezing drizzle",
-        "284" => "Heavy freezing drizzle",
-        "293" => "Patchy light rain",
-        "296" => "Light rain",
-        "299" => "Moderate rain at times",
-        "302" => "Moderate rain",
-        "305" => "Heavy rain at times",
-        "308" => "Heavy rain",
-        "311" => "Light freezing rain",
-        "314" => "Moderate or Heavy freezing rain",
-        "317" => "Light sleet",
-        "320" => "Moderate or heavy sleet",
-        "323" => "Patchy light snow",
-        "326" => "Light snow",
-        "329" => "Patchy moderate snow",
-        "332" => "Moderate snow",
-        "335" => "Patchy heavy snow",
-        "338" => "Heavy snow",
-        "350" => "Hail",
-        "353" => "Light rain shower",
-        "356" => "Moderate or heavy rain shower",
-        "359" => "Torrential rain shower",
-        "362" => "Light sleet showers",
-        "365" => "Moderate or heavy sleet showers",
-        "368" => "Light snow showers",
-        "371" => "Moderate or heavy snow showers",
-        "374" => "Light showers of hail",
-        "377" => "Moderate or heavy showers of hail",
-        "386" => "Patchy light rain in area with thunder",
-        "389" => "Moderate or heavy rain in area with thunder",
-        "392" => "Patchy light snow in area with thunder",
-        "395" => "Moderate or heavy snow in area with thunder",
-        _ => "invalid"
-    };
-
-    if weather_desc == "invalid" {
-        return format!("Invalid weather code: {}", weather_code);
-    }
-    
-    weather_desc.to_string()
-}
-
-pub fn get_weather_image(weather_code: &str, last_time_updated: &str, sunset: &str, force_day: bool) -> String {
-    let weather_image: &str = match is_night(last_time_updated, sunset, force_day) {
-        false => match weather_code {
-            "113" => "svg/wi-day-sunny.svg",
-            "116" => "svg/wi-day-cloudy.svg",
-            "119" => "svg/wi-day-cloudy.svg",
-            "122" => "svg/wi-day-sunny-overcast.svg",
-            "143" => "svg/wi-day-haze.svg",
-            "176" => "svg/wi-day-sprinkle.svg",
-            "179" => "svg/wi-day-snow.svg",
-            "182" => "svg/wi-day-sleet.svg",
-            "185" => "svg/wi-day-rain-mix.svg",
-            "200" => "svg/wi-day-rain-mix.svg",
-            "227" => "svg/wi-day-snow-wind.svg",
-            "230" => "svg/wi-day-snow-thunderstorm.svg",
-            "248" => "svg/wi-day-fog.svg",
-            "260" => "svg/wi-day-fog.svg",
-            "263" => "svg/wi-day-sprinkle.svg",
-            "266" => "svg/wi-day-sprinkle.svg",
-            "281" => "svg/wi-day-rain-mix.svg",
-            "284" => "svg/wi-day-rain-mix.svg",
-            "293" => "svg/wi-day-sprinkle.svg",
-            "296" => "svg/wi-day-rain.svg",
-            "299" => "svg/wi-day-rain.svg",
-            "302" => "svg/wi-day-rain.svg",
-            "305" => "svg/wi-day-rain.svg",
-            "308" => "svg/wi-day-rain.svg",
-            "311" => "svg/wi-day-rain-mix.svg",
-            "314" => "svg/wi-day-rain-mix.svg",
-            "317" => "svg/wi-day-sleet.svg",
-            "320" => "svg/wi-day-sleet.svg",
-            "323" => "svg/wi-day-snow.svg",
-            "326" => "svg/wi-day-snow.svg",
-            "329" => "svg/wi-day-snow.svg",
-            "332" => "svg/wi-day-snow.svg",
-            "335" => "svg/wi-day-snow-wind.svg",
-            "338" => "svg/wi-day-snow-wind.svg",
-            "350" => "svg/wi-day-hail.svg",
-            "353" => "svg/wi-day-rain.svg",
-            "356" => "svg/wi-day-rain.svg",
-            "359" => "svg/wi-day-thunderstorm.svg",
-            "362" => "svg/wi-day-sleet.svg",
-            "365" => "svg/wi-day-sleet-storm.svg",
-            "368" => "svg/wi-day-snow.svg",
-            "371" => "svg/wi-day-snow-wind.svg",
-            "374" => "svg/wi-day-hail.svg",
-            "377" => "svg/wi-day-hail.svg",
-            "386" => "svg/wi-day-rain.svg",
-            "389" => "svg/wi-day-thunderstorm.svg",
-            "392" => "svg/wi-day-snow-thunderstorm.svg",
-            "395" => "svg/wi-day-thunderstorm.svg",
-            "NA" => "svg/wi-na.svg",
-            _ => "svg/wi-na.svg",
-        },
-        true => match weather_code {
-            "113" => "svg/wi-night-clear.svg",
-            "116" => "svg/wi-night-partly-cloudy.svg",
-            "119" => "svg/wi-night-cloudy.svg",
-            "122" => "svg/wi-night-cloudy.svg",
-            "143" => "svg/wi-night-fog.svg",
-            "176" => "svg/wi-night-rain.svg",
-            "179" => "svg/wi-night-snow.svg",
-            "182" => "svg/wi-night-sleet.svg",
-            "185" => "svg/wi-night-rain-mix.svg",
-            "200" => "svg/wi-night-lightning.svg",
-            "227" => "svg/wi-night-snow-wind.svg",
-            "230" => "svg/wi-night-snow-wind.svg",
-            "248" => "svg/wi-night-fog.svg",
-            "260" => "svg/wi-night-fog.svg",
-            "263" => "svg/wi-night-rain.svg",
-            "266" => "svg/wi-night-rain.svg",
-            "281" => "svg/wi-night-rain-mix.svg",
-            "284" => "svg/wi-night-rain-mix.svg",
-            "293" => "svg/wi-night-rain.svg",
-            "296" => "svg/wi-night-rain.svg",
-            "299" => "svg/wi-night-rain.svg",
-            "302" => "svg/wi-night-rain.svg",
-            "305" => "svg/wi-night-storm-showers.svg",
-            "308" => "svg/wi-night-storm-showers.svg",
-            "311" => "svg/wi-night-rain-mix.svg",
-            "314" => "svg/wi-night-rain-mix.svg",
-            "317" => "svg/wi-night-sleet.svg",
-            "320" => "svg/wi-night-sleet-storm.svg",
-            "323" => "svg/wi-night-snow.svg",
-            "326" => "svg/wi-night-snow.svg",
-            "329" => "svg/wi-night-snow.svg",
-            "332" => "svg/wi-night-snow.svg",
-            "335" => "svg/wi-night-snow.svg",
-            "338" => "svg/wi-night-snow.svg",
-            "350" => "svg/wi-night-hail.svg",
-            "353" => "svg/wi-night-hail.svg",
-            "356" => "svg/wi-night-snow-thunderstorm.svg",
-            "359" => "svg/wi-night-thunderstorm.svg",
-            "362" => "svg/wi-night-sleet.svg",
-            "365" => "svg/wi-night-sleet-storm.svg",
-            "368" => "svg/wi-night-snow.svg",
-            "371" => "svg/wi-night-snow.svg",
-            "374" => "svg/wi-night-hail.svg",
-            "377" => "svg/wi-night-hail.svg",
-            "386" => "svg/wi-night-rain.svg",
-            "389" => "svg/wi-night-thunderstorm.svg",
-            "392" => "svg/wi-night-snow.svg",
-            "395" => "svg/wi-night-snow-thunderstorm.svg",
-            "NA" => "svg/wi-na.svg",
-            _ => "svg/wi-na.svg",
-        },
+/// wttr.in sends every number as a string, other providers send numbers or null.
+/// Anything that can't be read becomes `None` so one bad value doesn't throw out the whole forecast.
+fn deserialize_measurement<'de, D>(deserializer: D) -> Result<Option<f32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw_value: Value = Deserialize::deserialize(deserializer)?;
+    let measurement: Option<f32> = match raw_value {
+        Value::Number(x) => x.as_f64().map(|x| x as f32),
+        Value::String(x) => x.trim().parse::<f32>().ok(),
+        _ => None
     };
 
-    let current_directory: std::path::PathBuf = get_current_dir();
-    let file_path: std::path::PathBuf = current_directory.join(weather_image);
-    let svg_path: String = file_path.to_string_lossy().into_owned();
-
-    svg_path
+    Ok(measurement.filter(|x| x.is_finite()))
 }
 
-fn is_night(last_time_updated: &str, sunset: &str, force_day: bool) -> bool {
-    if force_day {
-        return false;
+pub fn format_measurement(measurement: Option<f32>) -> String {
+    match measurement {
+        Some(x) => format!("{:.0}", x),
+        None => "--".to_string()
     }
+}
 
-    let updated_time: NaiveTime = match NaiveTime::parse_from_str(last_time_updated, "%I:%M %P") {
-            Ok(time) => time,
-            Err(_) => NaiveTime::default(),
-    };
-
-    let night_time: NaiveTime = match NaiveTime::parse_from_str(sunset, "%I:%M %p") {
-        Ok(time) => time,
-        Err(_) => NaiveTime::default(),
-    };
-
-    if updated_time != NaiveTime::default() && night_time != NaiveTime::default() {
-        updated_time > night_time
-    } else {
-        false
+/// Night is before sunrise or after sunset. The time and the sun times are all the forecast location's local time.
+pub fn is_night(local_time: NaiveTime, sunrise: &str, sunset: &str) -> bool {
+    match (parse_astronomy_time(sunrise), parse_astronomy_time(sunset)) {
+        (Some(sunrise_time), Some(sunset_time)) => local_time < sunrise_time || local_time >= sunset_time,
+        _ => false
     }
 }
 
-fn save_weather_json(json_text: String) -> Result<(), io::Error> {
-    let settings_file: std::path::PathBuf = get_current_dir();
-    let file_path: std::path::PathBuf = settings_file.join("last_weather.json");
-    if file_path.exists() {
-        remove_file(&file_path)?;
+/// Sun and moon times look like "07:12 AM", wttr.in uses "No moonrise" on days without one.
+pub fn parse_astronomy_time(astronomy_time: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(astronomy_time.trim(), "%I:%M %p").ok()
+}
+
+/// A notable condition only takes over the day once it covers this share of the weighted hours...
+const NOTABLE_SHARE_PERCENT: u32 = 25;
+/// ...or lasts this many daylight hours.
+const NOTABLE_DAYLIGHT_HOURS: u32 = 2;
+
+/// Summarizes a day's hours into one condition. Each entry counts for the minutes until the
+/// next one, so wttr.in's three hour slots and the hourly providers weigh the same, and
+/// daylight counts double since that's when the forecast matters most. A notable condition
+/// wins over fair weather when it lasts long enough to matter, one stray thunderstorm hour
+/// doesn't. Ties go to the more severe condition so the result doesn't depend on iteration order.
+fn get_daily_weather_code(hourly_weather: &[ForecastHour], sunrise: &str, sunset: &str) -> String {
+    let start_times: Vec<Option<NaiveTime>> = hourly_weather.iter().map(|hour| hour.start_time()).collect();
+
+    // Each condition's weight and how many of its minutes are in daylight.
+    let mut weights: Vec<(WeatherCondition, u32, u32)> = Vec::new();
+    for (index, hour) in hourly_weather.iter().enumerate() {
+        let condition: WeatherCondition = WeatherCondition::from_code(&hour.weather_code);
+        if condition == WeatherCondition::Unknown {
+            continue;
+        }
+
+        let minutes: u32 = covered_minutes(start_times[index], start_times.get(index + 1).copied());
+        let daylight: bool = match start_times[index] {
+            Some(x) => !is_night(x, sunrise, sunset),
+            None => true
+        };
+        let (weight, daylight_minutes): (u32, u32) = match daylight {
+            true => (minutes * 2, minutes),
+            false => (minutes, 0)
+        };
+        match weights.iter_mut().find(|(x, _, _)| *x == condition) {
+            Some((_, total, total_daylight)) => {
+                *total += weight;
+                *total_daylight += daylight_minutes;
+            },
+            None => weights.push((condition, weight, daylight_minutes))
+        }
     }
 
-    let mut file: File = File::create(file_path)?;
-    file.write_all(json_text.as_bytes())?;
+    let total_weight: u32 = weights.iter().map(|(_, weight, _)| weight).sum();
+    let most_severe: Option<WeatherCondition> = weights.iter()
+        .filter(|(condition, weight, daylight_minutes)| {
+            condition.is_notable()
+                && (weight * 100 >= total_weight * NOTABLE_SHARE_PERCENT || *daylight_minutes >= NOTABLE_DAYLIGHT_HOURS * 60)
+        })
+        .map(|(condition, _, _)| *condition)
+        .max_by_key(|x| x.severity());
+    let daily_condition: WeatherCondition = match most_severe {
+        Some(x) => x,
+        None => weights.iter()
+            .max_by_key(|(condition, weight, _)| (*weight, condition.severity()))
+            .map(|(condition, _, _)| *condition)
+            .unwrap_or(WeatherCondition::Unknown)
+    };
 
-    Ok(())
+    daily_condition.code().to_string()
 }
 
-fn get_daily_weather_code(hourly_weather: Vec<ForecastHour>) -> String {
-    let mut weather_codes: HashMap<String, i16> = HashMap::new();
-    for hour in hourly_weather {
-        let weather_code: String = hour.weather_code;
-        *weather_codes.entry(weather_code).or_insert(0) += 1;
-    }
+/// Minutes from an entry's start until the next entry's, or until midnight for the day's last
+/// entry. Entries without a readable time count as one hour.
+fn covered_minutes(start: Option<NaiveTime>, next_start: Option<Option<NaiveTime>>) -> u32 {
+    let start_minute: u32 = match start {
+        Some(x) => x.num_seconds_from_midnight() / 60,
+        None => return 60
+    };
+    let end_minute: u32 = match next_start {
+        Some(Some(x)) => x.num_seconds_from_midnight() / 60,
+        Some(None) => start_minute + 60,
+        None => 24 * 60
+    };
 
-    weather_codes.into_iter().max_by_key(|&(_, count)| count).map(|(key, _)| key).expect("No weather codes available.")
+    match end_minute > start_minute {
+        true => end_minute - start_minute,
+        false => 60
+    }
 }
 
 fn reformat_date(raw_date: String) -> Result<String, ParseError> {
@@ -483,9 +770,15 @@ fn reformat_date(raw_date: String) -> Result<String, ParseError> {
     Ok(proc_date.format("%A %B %e, %Y").to_string())
 }
 
+/// Forecast hours are written as hours and minutes with no separator, "0", "300" or "2100".
+fn parse_raw_time(raw_time: &str) -> Option<NaiveTime> {
+    let hours_minutes: u32 = raw_time.trim().parse::<u32>().ok()?;
+
+    NaiveTime::from_hms_opt(hours_minutes / 100, hours_minutes % 100, 0)
+}
+
 fn reformat_time(raw_time: String, hour_12: bool) -> (String, NaiveTime) {
-    let temp_hour: u32 = raw_time.replace("00", "").parse::<u32>().unwrap();
-    let proc_time = match NaiveTime::from_hms_opt(temp_hour, 0, 0){
+    let proc_time = match parse_raw_time(&raw_time) {
         Some(x) => x,
         None => {
             println!("Error getting time for hourly forecast: {raw_time}");
@@ -499,4 +792,131 @@ fn reformat_time(raw_time: String, hour_12: bool) -> (String, NaiveTime) {
     };
 
     (proc_time.format(time_format).to_string(), proc_time)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_file_names_follow_the_query() {
+        let home: Location = Location { zip_code: "70808".to_string(), ..Location::default() };
+        assert_eq!(home.cache_file_name(), "last_weather_c3860437b190b6d6.json");
+
+        // Names that used to boil down to the same file no longer collide.
+        let cabin: Location = Location { name: "Cabin!".to_string(), city: "Asheville".to_string(), ..Location::default() };
+        let other_cabin: Location = Location { name: "cabin".to_string(), city: "Gatlinburg".to_string(), ..Location::default() };
+        let unnamed: Location = Location { latitude: Some(21.3069), longitude: Some(-157.8583), ..Location::default() };
+        assert_ne!(cabin.cache_file_name(), other_cabin.cache_file_name());
+        assert_ne!(home.cache_file_name(), unnamed.cache_file_name());
+
+        let renamed_home: Location = Location { name: "Home".to_string(), ..home.clone() };
+        assert_eq!(renamed_home.cache_file_name(), home.cache_file_name());
+    }
+
+    #[test]
+    fn empty_forecasts_are_parse_errors() {
+        let current: CurrentWeather = CurrentWeather { current_condition: Vec::new() };
+        let daily: DailyForecast = DailyForecast { weather: Vec::new() };
+        let forecast: Result<Weather, WeatherError> = Weather::new(current, daily, "09:00 am".to_string(), "10/18/26".to_string(), false, None);
+        assert!(matches!(forecast, Err(WeatherError::Parse { .. })));
+    }
+
+    #[test]
+    fn alert_summaries_fit_the_banner() {
+        let mut alert: WeatherAlert = WeatherAlert {
+            headline: "Flash Flood Warning".to_string(),
+            severity: AlertSeverity::Severe,
+            effective: Local::now(),
+            expires: Local::now() + Duration::hours(3),
+            description: "* WHAT...Flash flooding caused by\nexcessive rainfall.\n\n* WHERE...Oahu.".to_string(),
+        };
+        assert_eq!(alert.summary(), "* WHAT...Flash flooding caused by excessive rainfall.");
+        assert!(!alert.is_upcoming());
+
+        alert.description = "Heavy rain ".repeat(30);
+        // Cut at the last whole word that fits.
+        assert_eq!(alert.summary(), format!("{}Heavy...", "Heavy rain ".repeat(14)));
+    }
+
+    /// Three hour slots like wttr.in's, the sun is up from 6 AM to 6 PM.
+    fn daily_code(slots: &[(&str, WeatherCondition)]) -> WeatherCondition {
+        let hours: Vec<ForecastHour> = slots.iter()
+            .map(|(raw_time, condition)| ForecastHour {
+                raw_time: raw_time.to_string(),
+                time: String::new(),
+                temp: None,
+                feels_like: None,
+                chance_of_rain: None,
+                chance_of_snow: None,
+                precip: None,
+                wind_speed: None,
+                wind_gust: None,
+                wind_dir_degree: None,
+                wind_dir: String::new(),
+                weather_code: condition.code().to_string(),
+                weather_desc: String::new(),
+                weather_image: String::new(),
+            })
+            .collect();
+
+        WeatherCondition::from_code(&get_daily_weather_code(&hours, "06:00 AM", "06:00 PM"))
+    }
+
+    #[test]
+    fn equal_weights_go_to_the_more_severe_condition() {
+        use WeatherCondition::{Clear, Cloudy, LightRain};
+
+        let slots: [(&str, WeatherCondition); 5] = [("600", Cloudy), ("900", LightRain), ("1200", Cloudy), ("1500", LightRain), ("1800", Clear)];
+        assert_eq!(daily_code(&slots), LightRain);
+        let reversed: [(&str, WeatherCondition); 5] = [("600", LightRain), ("900", Cloudy), ("1200", LightRain), ("1500", Cloudy), ("1800", Clear)];
+        assert_eq!(daily_code(&reversed), LightRain);
+    }
+
+    /// One hour slots like Open-Meteo's and the National Weather Service's, starting at midnight.
+    fn hourly_daily_code(conditions: &[WeatherCondition; 24]) -> WeatherCondition {
+        let raw_times: Vec<String> = (0..24).map(|hour| (hour * 100).to_string()).collect();
+        let slots: Vec<(&str, WeatherCondition)> = raw_times.iter()
+            .map(|raw_time| raw_time.as_str())
+            .zip(conditions.iter().copied())
+            .collect();
+
+        daily_code(&slots)
+    }
+
+    #[test]
+    fn daylight_hours_outweigh_night_hours() {
+        use WeatherCondition::{Clear, PartlyCloudy};
+
+        // Fourteen clear hours, twelve of them at night, weigh 16. Ten partly cloudy daytime hours weigh 20.
+        let mut conditions: [WeatherCondition; 24] = [Clear; 24];
+        conditions[6..16].fill(PartlyCloudy);
+        assert_eq!(hourly_daily_code(&conditions), PartlyCloudy);
+    }
+
+    #[test]
+    fn notable_conditions_need_to_last() {
+        use WeatherCondition::{Clear, ModerateOrHeavyRainWithThunder as Thunder};
+
+        let mut one_stray_storm: [WeatherCondition; 24] = [Clear; 24];
+        one_stray_storm[12] = Thunder;
+        assert_eq!(hourly_daily_code(&one_stray_storm), Clear);
+
+        // A three hour slot covers three daylight hours of storms.
+        let noon_storm: [(&str, WeatherCondition); 8] = [
+            ("0", Clear), ("300", Clear), ("600", Clear), ("900", Clear),
+            ("1200", Thunder), ("1500", Clear), ("1800", Clear), ("2100", Clear),
+        ];
+        assert_eq!(daily_code(&noon_storm), Thunder);
+
+        let mut afternoon_storms: [WeatherCondition; 24] = [Clear; 24];
+        afternoon_storms[13..15].fill(Thunder);
+        assert_eq!(hourly_daily_code(&afternoon_storms), Thunder);
+
+        // No daylight hours, but a quarter of the day's weight.
+        let overnight_storms: [(&str, WeatherCondition); 8] = [
+            ("0", Thunder), ("300", Thunder), ("600", Clear), ("900", Clear),
+            ("1200", Clear), ("1500", Clear), ("1800", Clear), ("2100", Thunder),
+        ];
+        assert_eq!(daily_code(&overnight_storms), Thunder);
+    }
+}
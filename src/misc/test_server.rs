@@ -0,0 +1,62 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+/// A stand-in for a provider or feed in tests. Each request gets the payload saved for its
+/// path, the query string is ignored. `{base_url}` in a payload is replaced with the server's
+/// own address so payloads can link to other paths on it.
+pub fn serve_payloads(routes: &[(&str, u16, &str)]) -> String {
+    serve_requests(routes, usize::MAX)
+}
+
+/// Like `serve_payloads`, but the server shuts down after answering `requests` requests,
+/// for testing what happens when a server that was working goes away.
+pub fn serve_requests(routes: &[(&str, u16, &str)], requests: usize) -> String {
+    let listener: TcpListener = TcpListener::bind("127.0.0.1:0").expect("Couldn't start the test server.");
+    let base_url: String = format!("http://{}", listener.local_addr().unwrap());
+    let routes: Vec<(String, u16, String)> = routes.iter()
+        .map(|(path, status, payload)| (path.to_string(), *status, payload.replace("{base_url}", &base_url)))
+        .collect();
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten().take(requests) {
+            answer_request(stream, &routes);
+        }
+    });
+
+    base_url
+}
+
+/// An address nothing is listening on, for testing what happens when a server is down.
+pub fn unreachable_url() -> String {
+    let listener: TcpListener = TcpListener::bind("127.0.0.1:0").expect("Couldn't reserve a port.");
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+fn answer_request(mut stream: TcpStream, routes: &[(String, u16, String)]) {
+    let mut reader: BufReader<&TcpStream> = BufReader::new(&stream);
+    let mut request_line: String = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+
+    // Only the request line matters, skip the headers.
+    loop {
+        let mut header_line: String = String::new();
+        match reader.read_line(&mut header_line) {
+            Ok(x) if x > 2 => continue,
+            _ => break
+        }
+    }
+
+    let path: &str = request_line.split_whitespace()
+        .nth(1)
+        .and_then(|x| x.split('?').next())
+        .unwrap_or("/");
+    let (status, payload): (u16, &str) = match routes.iter().find(|(route_path, _, _)| route_path == path) {
+        Some((_, status, payload)) => (*status, payload.as_str()),
+        None => (404, "Not Found")
+    };
+
+    let _ = write!(stream, "HTTP/1.1 {status} Test\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{payload}", payload.len());
+}
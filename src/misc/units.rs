@@ -0,0 +1,281 @@
+use serde::{Deserialize, Serialize};
+
+use crate::misc::format_measurement;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureUnit {
+    #[serde(alias = "F", alias = "f", alias = "fahrenheit")]
+    Fahrenheit,
+    #[serde(alias = "C", alias = "c", alias = "celsius")]
+    Celsius,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeedUnit {
+    #[serde(alias = "mph")]
+    MilesPerHour,
+    #[serde(alias = "kmh", alias = "km/h")]
+    KilometersPerHour,
+    #[serde(alias = "ms", alias = "m/s")]
+    MetersPerSecond,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PressureUnit {
+    #[serde(alias = "hpa", alias = "hPa", alias = "mb")]
+    Hectopascals,
+    #[serde(alias = "inhg", alias = "inHg")]
+    InchesOfMercury,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceUnit {
+    #[serde(alias = "km")]
+    Kilometers,
+    #[serde(alias = "mi", alias = "miles")]
+    Miles,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrecipitationUnit {
+    #[serde(alias = "mm")]
+    Millimeters,
+    #[serde(alias = "in", alias = "inches")]
+    Inches,
+}
+
+/// Every measurement in the weather model is metric (°C, km/h, hPa, km and mm),
+/// the unit system converts it for display.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnitSystem {
+    pub temperature: TemperatureUnit,
+    pub wind_speed: SpeedUnit,
+    pub pressure: PressureUnit,
+    pub visibility: DistanceUnit,
+    pub precipitation: PrecipitationUnit,
+}
+
+/// Per quantity replacements for the units picked by `unit` in settings.json.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default)]
+pub struct UnitOverrides {
+    #[serde(default)]
+    pub temperature: Option<TemperatureUnit>,
+    #[serde(default)]
+    pub wind_speed: Option<SpeedUnit>,
+    #[serde(default)]
+    pub pressure: Option<PressureUnit>,
+    #[serde(default)]
+    pub visibility: Option<DistanceUnit>,
+    #[serde(default)]
+    pub precipitation: Option<PrecipitationUnit>,
+}
+
+impl Default for UnitSystem {
+    fn default() -> Self {
+        UnitSystem::imperial()
+    }
+}
+
+impl UnitSystem {
+    pub fn imperial() -> UnitSystem {
+        UnitSystem {
+            temperature: TemperatureUnit::Fahrenheit,
+            wind_speed: SpeedUnit::MilesPerHour,
+            pressure: PressureUnit::InchesOfMercury,
+            visibility: DistanceUnit::Miles,
+            precipitation: PrecipitationUnit::Inches,
+        }
+    }
+
+    pub fn metric() -> UnitSystem {
+        UnitSystem {
+            temperature: TemperatureUnit::Celsius,
+            wind_speed: SpeedUnit::KilometersPerHour,
+            pressure: PressureUnit::Hectopascals,
+            visibility: DistanceUnit::Kilometers,
+            precipitation: PrecipitationUnit::Millimeters,
+        }
+    }
+
+    /// Celsius with miles and miles per hour, the way UK forecasts read.
+    pub fn uk() -> UnitSystem {
+        UnitSystem {
+            temperature: TemperatureUnit::Celsius,
+            wind_speed: SpeedUnit::MilesPerHour,
+            pressure: PressureUnit::Hectopascals,
+            visibility: DistanceUnit::Miles,
+            precipitation: PrecipitationUnit::Millimeters,
+        }
+    }
+
+    pub fn from_name(unit_name: &str) -> Option<UnitSystem> {
+        match unit_name.to_lowercase().as_str() {
+            "f" | "imperial" | "us" => Some(UnitSystem::imperial()),
+            "c" | "metric" => Some(UnitSystem::metric()),
+            "uk" => Some(UnitSystem::uk()),
+            _ => None
+        }
+    }
+
+    pub fn with_overrides(self, overrides: &UnitOverrides) -> UnitSystem {
+        UnitSystem {
+            temperature: overrides.temperature.unwrap_or(self.temperature),
+            wind_speed: overrides.wind_speed.unwrap_or(self.wind_speed),
+            pressure: overrides.pressure.unwrap_or(self.pressure),
+            visibility: overrides.visibility.unwrap_or(self.visibility),
+            precipitation: overrides.precipitation.unwrap_or(self.precipitation),
+        }
+    }
+
+    pub fn temperature(&self, celsius: Option<f32>) -> String {
+        format!("{}{}", format_measurement(celsius.map(|x| self.convert_temperature(x))), self.temperature_symbol())
+    }
+
+    /// Numeric temperature in the display unit, for drawing rather than text.
+    pub fn convert_temperature(&self, celsius: f32) -> f32 {
+        match self.temperature {
+            TemperatureUnit::Fahrenheit => celsius_to_fahrenheit(celsius),
+            TemperatureUnit::Celsius => celsius,
+        }
+    }
+
+    pub fn temperature_symbol(&self) -> &'static str {
+        match self.temperature {
+            TemperatureUnit::Fahrenheit => "°F",
+            TemperatureUnit::Celsius => "°C",
+        }
+    }
+
+    pub fn wind_speed(&self, kmh: Option<f32>) -> String {
+        let (converted, symbol): (Option<f32>, &str) = match self.wind_speed {
+            SpeedUnit::MilesPerHour => (kmh.map(|x| x / 1.609_344), "mph"),
+            SpeedUnit::KilometersPerHour => (kmh, "km/h"),
+            SpeedUnit::MetersPerSecond => (kmh.map(|x| x / 3.6), "m/s"),
+        };
+
+        format!("{} {}", format_measurement(converted), symbol)
+    }
+
+    pub fn pressure(&self, hpa: Option<f32>) -> String {
+        match self.pressure {
+            PressureUnit::Hectopascals => format!("{} hPa", format_measurement(hpa)),
+            PressureUnit::InchesOfMercury => format!("{} inHg", format_decimals(hpa.map(|x| x * 0.029_53), 2)),
+        }
+    }
+
+    pub fn visibility(&self, km: Option<f32>) -> String {
+        match self.visibility {
+            DistanceUnit::Kilometers => format!("{} km", format_measurement(km)),
+            DistanceUnit::Miles => format!("{} mi", format_measurement(km.map(|x| x / 1.609_344))),
+        }
+    }
+
+    pub fn precipitation(&self, mm: Option<f32>) -> String {
+        match self.precipitation {
+            PrecipitationUnit::Millimeters => format!("{} mm", format_decimals(mm, 1)),
+            PrecipitationUnit::Inches => format!("{} in", format_decimals(mm.map(|x| x / 25.4), 2)),
+        }
+    }
+}
+
+pub fn celsius_to_fahrenheit(celsius: f32) -> f32 {
+    celsius * 9.0 / 5.0 + 32.0
+}
+
+pub fn fahrenheit_to_celsius(fahrenheit: f32) -> f32 {
+    (fahrenheit - 32.0) * 5.0 / 9.0
+}
+
+const COMPASS_POINTS: [&str; 16] = [
+    "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW", "NW", "NNW"
+];
+
+/// Wind direction in degrees to a 16 point compass name like wttr.in's `winddir16Point`.
+pub fn degrees_to_compass(degrees: Option<f32>) -> String {
+    match degrees {
+        Some(x) => COMPASS_POINTS[((x.rem_euclid(360.0) / 22.5).round() as usize) % 16].to_string(),
+        None => "".to_string()
+    }
+}
+
+pub fn compass_to_degrees(compass_point: &str) -> Option<f32> {
+    COMPASS_POINTS.iter()
+        .position(|x| x.eq_ignore_ascii_case(compass_point.trim()))
+        .map(|index| index as f32 * 22.5)
+}
+
+fn format_decimals(measurement: Option<f32>, decimals: usize) -> String {
+    match measurement {
+        Some(x) => format!("{:.*}", decimals, x),
+        None => "--".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_temperatures() {
+        assert_eq!(UnitSystem::imperial().temperature(Some(20.0)), "68°F");
+        assert_eq!(UnitSystem::metric().temperature(Some(20.0)), "20°C");
+        assert_eq!(UnitSystem::imperial().temperature(Some(-40.0)), "-40°F");
+        assert_eq!(UnitSystem::imperial().temperature(None), "--°F");
+        assert!((fahrenheit_to_celsius(celsius_to_fahrenheit(21.5)) - 21.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn converts_wind_speeds() {
+        assert_eq!(UnitSystem::imperial().wind_speed(Some(16.093_44)), "10 mph");
+        assert_eq!(UnitSystem::metric().wind_speed(Some(16.093_44)), "16 km/h");
+        assert_eq!(UnitSystem::metric().with_overrides(&UnitOverrides { wind_speed: Some(SpeedUnit::MetersPerSecond), ..UnitOverrides::default() }).wind_speed(Some(36.0)), "10 m/s");
+        assert_eq!(UnitSystem::metric().wind_speed(None), "-- km/h");
+    }
+
+    #[test]
+    fn converts_pressures() {
+        assert_eq!(UnitSystem::metric().pressure(Some(1013.25)), "1013 hPa");
+        assert_eq!(UnitSystem::imperial().pressure(Some(1013.25)), "29.92 inHg");
+        assert_eq!(UnitSystem::imperial().pressure(None), "-- inHg");
+    }
+
+    #[test]
+    fn converts_visibility() {
+        assert_eq!(UnitSystem::metric().visibility(Some(16.093_44)), "16 km");
+        assert_eq!(UnitSystem::imperial().visibility(Some(16.093_44)), "10 mi");
+        assert_eq!(UnitSystem::uk().visibility(Some(16.093_44)), "10 mi");
+    }
+
+    #[test]
+    fn converts_precipitation() {
+        assert_eq!(UnitSystem::metric().precipitation(Some(25.4)), "25.4 mm");
+        assert_eq!(UnitSystem::imperial().precipitation(Some(25.4)), "1.00 in");
+        assert_eq!(UnitSystem::imperial().precipitation(Some(0.0)), "0.00 in");
+        assert_eq!(UnitSystem::metric().precipitation(None), "-- mm");
+    }
+
+    #[test]
+    fn overrides_replace_single_quantities() {
+        let overrides: UnitOverrides = serde_json::from_str(r#"{ "wind_speed": "m/s", "visibility": "mi" }"#).unwrap();
+        let units: UnitSystem = UnitSystem::from_name("metric").unwrap().with_overrides(&overrides);
+
+        assert_eq!(units, UnitSystem {
+            temperature: TemperatureUnit::Celsius,
+            wind_speed: SpeedUnit::MetersPerSecond,
+            pressure: PressureUnit::Hectopascals,
+            visibility: DistanceUnit::Miles,
+            precipitation: PrecipitationUnit::Millimeters,
+        });
+        assert_eq!(units.temperature(Some(20.0)), "20°C");
+        assert_eq!(units.wind_speed(Some(36.0)), "10 m/s");
+        assert_eq!(units.visibility(Some(16.093_44)), "10 mi");
+        assert_eq!(units.precipitation(Some(2.0)), "2.0 mm");
+
+        // UK forecasts mix Celsius with miles per hour.
+        let uk: UnitSystem = UnitSystem::from_name("UK").unwrap();
+        assert_eq!(uk.temperature(Some(20.0)), "20°C");
+        assert_eq!(uk.wind_speed(Some(16.093_44)), "10 mph");
+        assert_eq!(uk.pressure(Some(1013.25)), "1013 hPa");
+        assert_eq!(UnitSystem::from_name("kelvin"), None);
+    }
+}
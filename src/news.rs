@@ -0,0 +1,4 @@
+pub use self::feed::{Headline, parse_feed};
+pub use self::get_news::get_news;
+mod feed;
+mod get_news;
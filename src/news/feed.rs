@@ -0,0 +1,205 @@
+use chrono::{DateTime, Local};
+use roxmltree::{Document, Node};
+use serde::{Deserialize, Serialize};
+
+use crate::misc::FeedError;
+
+/// One story from a feed.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Headline {
+    pub title: String,
+    pub source: String,
+    #[serde(default)]
+    pub link: String,
+    /// The guid or Atom id, falling back to the link.
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub published: Option<DateTime<Local>>,
+}
+
+impl Headline {
+    /// Stories picked up by more than one feed usually keep their title, so that's compared too.
+    pub fn is_duplicate_of(&self, other: &Headline) -> bool {
+        (!self.id.is_empty() && self.id == other.id)
+            || normalize_title(&self.title) == normalize_title(&other.title)
+    }
+}
+
+/// Reads the items out of an RSS 2.0, RSS 1.0 or Atom feed.
+/// `source_name` is shown with each headline, the feed's own title is used when it's empty.
+pub fn parse_feed(xml: &str, source_name: &str) -> Result<Vec<Headline>, FeedError> {
+    let document: Document = match Document::parse(xml) {
+        Ok(x) => x,
+        Err(xml_error) => return Err(FeedError::Parse { msg: xml_error.to_string() })
+    };
+
+    let root: Node = document.root_element();
+    let source: String = match source_name.is_empty() {
+        true => feed_title(root),
+        false => source_name.to_string()
+    };
+
+    let headlines: Vec<Headline> = match root.tag_name().name() {
+        "feed" => root.children()
+            .filter(|node| node.has_tag_name("entry"))
+            .filter_map(|entry| parse_atom_entry(entry, &source))
+            .collect(),
+        "rss" | "RDF" => root.descendants()
+            .filter(|node| node.has_tag_name("item"))
+            .filter_map(|item| parse_rss_item(item, &source))
+            .collect(),
+        other => return Err(FeedError::Parse { msg: format!("Not an RSS or Atom feed, found <{other}>.") })
+    };
+
+    Ok(headlines)
+}
+
+fn parse_rss_item(item: Node, source: &str) -> Option<Headline> {
+    let title: String = child_text(item, "title")?;
+    let link: String = child_text(item, "link").unwrap_or_default();
+
+    // RSS 1.0 puts the date in Dublin Core.
+    let published: Option<DateTime<Local>> = child_text(item, "pubDate")
+        .or_else(|| child_text(item, "date"))
+        .and_then(|date| parse_feed_date(&date));
+
+    Some(Headline {
+        id: child_text(item, "guid").unwrap_or_else(|| link.clone()),
+        title,
+        source: source.to_string(),
+        link,
+        published,
+    })
+}
+
+fn parse_atom_entry(entry: Node, source: &str) -> Option<Headline> {
+    let title: String = child_text(entry, "title")?;
+    let link: String = entry.children()
+        .filter(|node| node.has_tag_name("link"))
+        .find(|node| matches!(node.attribute("rel"), None | Some("alternate")))
+        .and_then(|node| node.attribute("href"))
+        .unwrap_or_default()
+        .to_string();
+    let published: Option<DateTime<Local>> = child_text(entry, "published")
+        .or_else(|| child_text(entry, "updated"))
+        .and_then(|date| parse_feed_date(&date));
+
+    Some(Headline {
+        id: child_text(entry, "id").unwrap_or_else(|| link.clone()),
+        title,
+        source: source.to_string(),
+        link,
+        published,
+    })
+}
+
+fn feed_title(root: Node) -> String {
+    let title_parent: Node = root.children().find(|node| node.has_tag_name("channel")).unwrap_or(root);
+    child_text(title_parent, "title").unwrap_or_default()
+}
+
+/// Text of the first child with this name, ignoring namespaces. Empty text counts as missing.
+fn child_text(node: Node, name: &str) -> Option<String> {
+    let child: Node = node.children().find(|child| child.has_tag_name(name))?;
+    let text: String = child.descendants()
+        .filter(|x| x.is_text())
+        .filter_map(|x| x.text())
+        .collect::<String>();
+    let text: String = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    match text.is_empty() {
+        true => None,
+        false => Some(text)
+    }
+}
+
+/// RSS uses RFC 2822 dates and Atom uses RFC 3339.
+fn parse_feed_date(date: &str) -> Option<DateTime<Local>> {
+    DateTime::parse_from_rfc2822(date)
+        .or_else(|_| DateTime::parse_from_rfc3339(date))
+        .map(|x| x.with_timezone(&Local))
+        .ok()
+}
+
+fn normalize_title(title: &str) -> String {
+    title.chars()
+        .filter(|x| x.is_alphanumeric() || x.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    const RSS_2: &str = include_str!("../../tests/fixtures/news_rss2.xml");
+    const RSS_1: &str = include_str!("../../tests/fixtures/news_rss1.xml");
+    const ATOM: &str = include_str!("../../tests/fixtures/news_atom.xml");
+
+    fn utc_time(published: &Option<DateTime<Local>>) -> Option<String> {
+        published.map(|x| x.with_timezone(&Utc).format("%FT%RZ").to_string())
+    }
+
+    #[test]
+    fn reads_rss_2() {
+        let headlines: Vec<Headline> = parse_feed(RSS_2, "").unwrap();
+
+        // The item without a title is left out.
+        assert_eq!(headlines.len(), 3);
+        assert!(headlines.iter().all(|headline| headline.source == "Mirror Daily"));
+
+        assert_eq!(headlines[0].title, "City Council Approves New Park!");
+        assert_eq!(headlines[0].id, "daily-park-2026");
+        assert_eq!(utc_time(&headlines[0].published), Some("2026-10-17T13:05Z".to_string()));
+
+        // CDATA with runs of whitespace, and a date with an offset.
+        assert_eq!(headlines[1].title, "Storms expected this weekend");
+        assert_eq!(utc_time(&headlines[1].published), Some("2026-10-17T14:30Z".to_string()));
+
+        // Without a guid the link is the id.
+        assert_eq!(headlines[2].id, "https://daily.example.com/letters");
+        assert_eq!(headlines[2].published, None);
+    }
+
+    #[test]
+    fn reads_rss_1() {
+        let headlines: Vec<Headline> = parse_feed(RSS_1, "Science").unwrap();
+
+        let titles: Vec<&str> = headlines.iter().map(|headline| headline.title.as_str()).collect();
+        assert_eq!(titles, ["Comet visible before sunrise", "City council approves new park"]);
+        assert_eq!(headlines[0].source, "Science");
+        assert_eq!(headlines[0].link, "https://science.example.org/comet");
+        assert_eq!(utc_time(&headlines[0].published), Some("2026-10-17T11:45Z".to_string()));
+    }
+
+    #[test]
+    fn reads_atom() {
+        let headlines: Vec<Headline> = parse_feed(ATOM, "").unwrap();
+
+        assert_eq!(headlines.len(), 2);
+        assert_eq!(headlines[0].source, "Weather Wire");
+        assert_eq!(headlines[0].id, "tag:weather.example.com,2026:storms");
+        // The alternate link is the story, not the self link.
+        assert_eq!(headlines[0].link, "https://weather.example.com/storms");
+        assert_eq!(utc_time(&headlines[0].published), Some("2026-10-17T14:00Z".to_string()));
+
+        // No published date, so the updated one is used.
+        assert_eq!(headlines[1].link, "https://weather.example.com/frost");
+        assert_eq!(utc_time(&headlines[1].published), Some("2026-10-17T13:15Z".to_string()));
+    }
+
+    #[test]
+    fn rejects_what_isnt_a_feed() {
+        for not_a_feed in ["<html><body>Moved</body></html>", "<rss><channel>", ""] {
+            match parse_feed(not_a_feed, "") {
+                Err(FeedError::Parse { .. }) => (),
+                other => panic!("Expected a parse error for {:?}, got {:?}", not_a_feed, other)
+            }
+        }
+    }
+}
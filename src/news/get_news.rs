@@ -0,0 +1,199 @@
+use crate::misc::{FeedError, NewsFeed};
+use crate::news::{Headline, parse_feed};
+use chrono::{DateTime, Local};
+use reqwest::{Client, Response, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fs::{read_to_string, remove_file, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const NEWS_CACHE_FILE: &str = "last_news.json";
+
+/// The last headlines read from each feed, so the ticker still has something to show offline.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+struct CachedNews {
+    feeds: Vec<CachedFeed>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct CachedFeed {
+    url: String,
+    fetched_at: DateTime<Local>,
+    headlines: Vec<Headline>,
+}
+
+/// Headlines from every feed, newest first with duplicates removed.
+/// Feeds that can't be reached use their saved headlines instead, kept in `cache_dir`.
+pub async fn get_news(feeds: &[NewsFeed], max_headlines: usize, cache_dir: &Path) -> Result<Vec<Headline>, FeedError> {
+    let client: Client = Client::builder().use_rustls_tls().build().unwrap();
+    let mut cached_news: CachedNews = get_saved_news(cache_dir);
+
+    let mut headlines: Vec<Headline> = Vec::new();
+    let mut fetch_error: Option<FeedError> = None;
+    let mut fetched_any: bool = false;
+    for feed in feeds {
+        let fetched: Result<Vec<Headline>, FeedError> = match fetch_feed(&client, &feed.url).await {
+            Ok(xml) => parse_feed(&xml, &feed.name),
+            Err(network_error) => Err(network_error)
+        };
+
+        match fetched {
+            Ok(feed_headlines) => {
+                cached_news.feeds.retain(|cached_feed| cached_feed.url != feed.url);
+                cached_news.feeds.push(CachedFeed {
+                    url: feed.url.clone(),
+                    fetched_at: Local::now(),
+                    headlines: feed_headlines.clone(),
+                });
+                headlines.extend(feed_headlines);
+                fetched_any = true;
+            },
+            Err(feed_error) => {
+                println!("Error getting news from {}: {}", feed.url, feed_error);
+                match cached_news.feeds.iter().find(|cached_feed| cached_feed.url == feed.url) {
+                    Some(cached_feed) => {
+                        println!("Using news from {} saved {}.", feed.url, cached_feed.fetched_at.format("%D %I:%M %P"));
+                        headlines.extend(cached_feed.headlines.clone());
+                    },
+                    None => fetch_error = Some(feed_error)
+                }
+            }
+        }
+    }
+
+    if fetched_any {
+        // Feeds that were taken out of settings don't need to be kept.
+        cached_news.feeds.retain(|cached_feed| feeds.iter().any(|feed| feed.url == cached_feed.url));
+        if let Err(save_error) = save_news(&cached_news, cache_dir) {
+            println!("Couldn't save news: {}", save_error);
+        }
+    }
+
+    if let (true, Some(news_error)) = (headlines.is_empty(), fetch_error) {
+        return Err(news_error);
+    }
+
+    Ok(merge_headlines(headlines, max_headlines))
+}
+
+/// Newest first, keeping the newest copy of a story that's in more than one feed.
+fn merge_headlines(mut headlines: Vec<Headline>, max_headlines: usize) -> Vec<Headline> {
+    // Undated headlines go last, the sort is stable so they keep their feed order.
+    headlines.sort_by_key(|headline| Reverse(headline.published));
+    let mut unique_headlines: Vec<Headline> = Vec::new();
+    for headline in headlines {
+        if !unique_headlines.iter().any(|x| headline.is_duplicate_of(x)) {
+            unique_headlines.push(headline);
+        }
+    }
+    unique_headlines.truncate(max_headlines);
+
+    unique_headlines
+}
+
+async fn fetch_feed(client: &Client, url: &str) -> Result<String, FeedError> {
+    let escaped_url: Url = match Url::parse(url) {
+        Ok(x) => x,
+        Err(url_error) => return Err(FeedError::Network { msg: url_error.to_string() })
+    };
+
+    let feed_response: Response = client.get(escaped_url).send().await?;
+    match feed_response.status() {
+        StatusCode::OK => Ok(feed_response.text().await?),
+        status_code => Err(FeedError::HttpStatus { http_code: status_code })
+    }
+}
+
+/// A missing or unreadable cache just means starting fresh.
+fn get_saved_news(cache_dir: &Path) -> CachedNews {
+    let file_path: PathBuf = cache_dir.join(NEWS_CACHE_FILE);
+    let saved_json: String = match read_to_string(file_path) {
+        Ok(x) => x,
+        Err(_) => return CachedNews::default()
+    };
+
+    match serde_json::from_str(saved_json.as_str()) {
+        Ok(x) => x,
+        Err(cache_error) => {
+            println!("Couldn't read saved news: {}", cache_error);
+            CachedNews::default()
+        }
+    }
+}
+
+fn save_news(cached_news: &CachedNews, cache_dir: &Path) -> Result<(), FeedError> {
+    let file_path: PathBuf = cache_dir.join(NEWS_CACHE_FILE);
+    if file_path.exists() {
+        remove_file(&file_path)?;
+    }
+
+    let saved_json: String = match serde_json::to_string(cached_news) {
+        Ok(x) => x,
+        Err(json_error) => return Err(FeedError::Parse { msg: json_error.to_string() })
+    };
+    let mut file: File = File::create(file_path)?;
+    file.write_all(saved_json.as_bytes())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_server::{serve_requests, unreachable_url};
+
+    const RSS_2: &str = include_str!("../../tests/fixtures/news_rss2.xml");
+    const RSS_1: &str = include_str!("../../tests/fixtures/news_rss1.xml");
+    const ATOM: &str = include_str!("../../tests/fixtures/news_atom.xml");
+
+    fn titles_and_sources(headlines: &[Headline]) -> Vec<(&str, &str)> {
+        headlines.iter().map(|headline| (headline.title.as_str(), headline.source.as_str())).collect()
+    }
+
+    #[test]
+    fn merges_feeds_without_duplicates() {
+        let mut headlines: Vec<Headline> = parse_feed(RSS_2, "").unwrap();
+        headlines.extend(parse_feed(RSS_1, "").unwrap());
+        headlines.extend(parse_feed(ATOM, "").unwrap());
+
+        // The park story is in two feeds under slightly different titles, the storm story
+        // is in two feeds with the same id. The newer copy of each is kept.
+        assert_eq!(titles_and_sources(&merge_headlines(headlines.clone(), 10)), [
+            ("Storms expected this weekend", "Mirror Daily"),
+            ("Frost advisory lifted", "Weather Wire"),
+            ("City Council Approves New Park!", "Mirror Daily"),
+            ("Comet visible before sunrise", "Science Notes"),
+            ("Letters to the editor", "Mirror Daily"),
+        ]);
+        assert_eq!(merge_headlines(headlines, 2).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn uses_saved_news_when_a_feed_is_down() {
+        let cache_dir: PathBuf = std::env::temp_dir().join(format!("mirror_news_test_{}", std::process::id()));
+        std::fs::create_dir_all(&cache_dir).unwrap();
+
+        // Answers once, then goes down.
+        let base_url: String = serve_requests(&[("/rss.xml", 200, RSS_2)], 1);
+        let feed: NewsFeed = NewsFeed { name: "Daily".to_string(), url: format!("{base_url}/rss.xml") };
+
+        let fetched: Vec<Headline> = get_news(std::slice::from_ref(&feed), 10, &cache_dir).await.unwrap();
+        assert_eq!(fetched.len(), 3);
+        let saved_feed: Option<CachedFeed> = get_saved_news(&cache_dir).feeds.into_iter().find(|cached_feed| cached_feed.url == feed.url);
+        assert_eq!(saved_feed.map(|cached_feed| cached_feed.headlines.len()), Some(3));
+
+        let offline: Vec<Headline> = get_news(std::slice::from_ref(&feed), 10, &cache_dir).await.unwrap();
+        assert_eq!(titles_and_sources(&offline), titles_and_sources(&fetched));
+
+        // A feed that's down with nothing saved is an error once there's nothing else to show.
+        let never_up: NewsFeed = NewsFeed { name: "".to_string(), url: format!("{}/rss.xml", unreachable_url()) };
+        match get_news(std::slice::from_ref(&never_up), 10, &cache_dir).await {
+            Err(FeedError::Network { .. }) => (),
+            other => panic!("Expected a network error, got {:?}", other)
+        }
+        assert_eq!(get_news(&[never_up, feed], 10, &cache_dir).await.unwrap().len(), 3);
+
+        std::fs::remove_dir_all(&cache_dir).unwrap();
+    }
+}
@@ -1,2 +1,10 @@
 pub use self::get_weather::get_weather;
+pub use self::provider::{WeatherProvider, get_providers, is_provider_name};
+pub use self::nws::NwsProvider;
+pub use self::open_meteo::OpenMeteoProvider;
+pub use self::wttr::WttrProvider;
 mod get_weather;
+mod nws;
+mod open_meteo;
+mod provider;
+mod wttr;
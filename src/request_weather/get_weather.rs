@@ -1,110 +1,149 @@
-use crate::misc::{RequestError, Weather, get_current_dir};
+use crate::misc::{WeatherError, Location, CachedWeather, Weather, WeatherAlert, get_current_dir};
+use crate::request_weather::WeatherProvider;
 use chrono::{DateTime, Local};
-use reqwest::{Url, StatusCode, Client, Response};
-use std::error::Error;
-use std::fs::{metadata, read_to_string};
-use std::io;
-
-pub async fn get_weather(zip_code: &str, hour_12: &bool) -> Result<Weather, Box<dyn Error>> {
-    let raw_url: String = format!("http://wttr.in/{zip_code}?format=j1");
-    let escaped_url: Url = match Url::parse(raw_url.as_str()){
-        Ok(url) => url,
-        Err(_) => {
-            let request_error: Box<RequestError> = Box::<RequestError>::new(
-                RequestError::new(StatusCode::NOT_FOUND),
-            );
-            return Err(request_error);
-        }
-    };
+use reqwest::Client;
+use std::fs::{read_to_string, remove_file, File};
+use std::io::Write;
+
+pub async fn get_weather(location: &Location, hour_12: &bool, cache_max_age_hours: &u32, providers: &[Box<dyn WeatherProvider>]) -> Result<Weather, WeatherError> {
     let client: Client = Client::builder().use_rustls_tls().build().unwrap();
 
-    let wttr_response: Response = match client.get(escaped_url).send().await {
-        Ok(resp) => resp,
-        Err(_) => {
-            let (saved_json, weather_time, weather_date): (String, String, String) =
-                match get_weather_from_saved_json() {
-                    Ok(json_data) => json_data,
-                    Err(_) => {
-                        let request_error: Box<RequestError> = Box::<RequestError>::new(
-                            RequestError::new(StatusCode::GATEWAY_TIMEOUT),
-                        );
-                        return Err(request_error);
-                    }
-                };
-            return Ok(Weather::new(
-                saved_json, 
-                weather_time, 
-                weather_date, 
-                hour_12.to_owned()
-            )?);
+    let mut fetch_error: WeatherError = WeatherError::Network { msg: "No weather providers were tried.".to_string() };
+    for provider in providers {
+        let weather_json: String = match provider.fetch(&client, location).await {
+            Ok(payload) => payload,
+            Err(provider_error) => {
+                println!("Error getting weather from {}: {}", provider.name(), provider_error);
+                fetch_error = provider_error;
+                continue;
+            }
+        };
+
+        let fetched_at: DateTime<Local> = Local::now();
+        let weather_update_time: String = fetched_at.format("%I:%M %P").to_string();
+        let weather_update_date: String = fetched_at.format("%D").to_string();
+        let mut weather_forecast: Weather = match provider.parse(weather_json.clone(), weather_update_time, weather_update_date, hour_12.to_owned()) {
+            Ok(x) => x,
+            Err(parse_error) => {
+                println!("Error reading weather from {}: {}", provider.name(), parse_error);
+                fetch_error = parse_error;
+                continue;
+            }
+        };
+
+        // The fresh forecast is still good when it can't be saved for later.
+        if let Err(save_error) = save_weather(&CachedWeather {
+            fetched_at,
+            provider: provider.name().to_string(),
+            location: location.clone(),
+            payload: weather_json,
+        }) {
+            println!("Couldn't save weather from {}: {}", provider.name(), save_error);
+        }
+        weather_forecast.alerts = get_alerts(&client, location, providers).await;
+        return Ok(weather_forecast);
+    }
+
+    let cached_weather: CachedWeather = match get_saved_weather(location) {
+        Ok(x) => x,
+        Err(cache_error) => {
+            println!("No saved weather to fall back on: {}", cache_error);
+            return Err(fetch_error);
         }
     };
 
-    match wttr_response.status() {
-        StatusCode::OK => {
-            let weather_json: String = wttr_response.text().await?;
-            if weather_json.contains("Unknown location; please try") {
-                let (saved_json, weather_time, weather_date): (String, String, String) =
-                    match get_weather_from_saved_json() {
-                        Ok(json_data) => json_data,
-                        Err(_) => {
-                            let request_error: Box<RequestError> = Box::<RequestError>::new(
-                                RequestError::new(StatusCode::TOO_MANY_REQUESTS),
-                            );
-                            return Err(request_error);
-                        }
-                    };
-                return Ok(Weather::new(
-                    saved_json, 
-                    weather_time, 
-                    weather_date,
-                    hour_12.to_owned()
-                )?);
-            } else {
-                let weather_update_time: String = Local::now().format("%I:%M %P").to_string();
-                let weather_update_date: String = Local::now().format("%D").to_string();
-                return Ok(Weather::new(
-                    weather_json,
-                    weather_update_time,
-                    weather_update_date,
-                    hour_12.to_owned()
-                )?);
-            }
+    if cached_weather.is_stale(*cache_max_age_hours) {
+        println!("Saved weather from {} is older than {} hours, not using it.", cached_weather.fetched_at.format("%D %I:%M %P"), cache_max_age_hours);
+        return Err(fetch_error);
+    }
+
+    let cached_provider: &dyn WeatherProvider = match providers.iter().find(|provider| provider.name() == cached_weather.provider) {
+        Some(x) => x.as_ref(),
+        None => {
+            println!("Saved weather is from {}, which isn't in providers anymore.", cached_weather.provider);
+            return Err(fetch_error);
         }
-        _ => {
-            let (saved_json, weather_time, weather_date): (String, String, String) =
-                match get_weather_from_saved_json() {
-                    Ok(json_data) => json_data,
-                    Err(_) => {
-                        let request_error: Box<RequestError> =
-                            Box::<RequestError>::new(RequestError::new(wttr_response.status()));
-                        return Err(request_error);
-                    }
-                };
-            return Ok(Weather::new(
-                saved_json, 
-                weather_time, 
-                weather_date,
-                hour_12.to_owned()
-            )?);
+    };
+
+    let mut weather_forecast: Weather = match cached_provider.parse(
+        cached_weather.payload.clone(),
+        cached_weather.fetched_at.format("%I:%M %P").to_string(),
+        cached_weather.fetched_at.format("%D").to_string(),
+        hour_12.to_owned()
+    ) {
+        Ok(x) => x,
+        Err(parse_error) => {
+            println!("Error reading saved weather from {}: {}", cached_provider.name(), parse_error);
+            return Err(fetch_error);
         }
     };
+
+    weather_forecast.cached_at = Some(cached_weather.fetched_at);
+    weather_forecast.alerts = get_alerts(&client, location, providers).await;
+    Ok(weather_forecast)
+}
+
+async fn get_alerts(client: &Client, location: &Location, providers: &[Box<dyn WeatherProvider>]) -> Vec<WeatherAlert> {
+    let mut alerts: Vec<WeatherAlert> = Vec::new();
+    for provider in providers {
+        match provider.fetch_alerts(client, location).await {
+            Ok(provider_alerts) => alerts.extend(provider_alerts),
+            Err(alert_error) => println!("Error getting alerts from {}: {}", provider.name(), alert_error)
+        }
+    }
+
+    alerts.retain(|alert| !alert.is_expired());
+    alerts
 }
 
-fn get_weather_from_saved_json() -> Result<(String, String, String), io::Error> {
+fn get_saved_weather(location: &Location) -> Result<CachedWeather, WeatherError> {
+    let settings_file: std::path::PathBuf = get_current_dir();
+    let file_path: std::path::PathBuf = settings_file.join(location.cache_file_name());
+
+    let saved_json: String = match read_to_string(file_path) {
+        Ok(x) => x,
+        Err(read_error) => return Err(WeatherError::CacheMiss { msg: read_error.to_string() })
+    };
+    let cached_weather: CachedWeather = serde_json::from_str(saved_json.as_str())?;
+
+    // Guard against a hash collision or a hand-edited file.
+    if cached_weather.location.query() != location.query() {
+        return Err(WeatherError::CacheMiss { msg: "Saved weather is for a different location.".to_string() });
+    }
 
+    Ok(cached_weather)
+}
+
+fn save_weather(cached_weather: &CachedWeather) -> Result<(), WeatherError> {
     let settings_file: std::path::PathBuf = get_current_dir();
-    let file_path: std::path::PathBuf = settings_file.join("last_weather.json");
-    let file_name: &str = file_path.to_str().expect("Invalid saved weather file.");
+    let file_path: std::path::PathBuf = settings_file.join(cached_weather.location.cache_file_name());
+    if file_path.exists() {
+        remove_file(&file_path)?;
+    }
 
-    let saved_json: String = read_to_string(file_name)?;
-    let json_modified: DateTime<Local> = metadata(file_name)?.modified()?.into();
-    let json_time: String = json_modified.format("%I:%M %P").to_string();
-    let json_date: String = json_modified.format("%D").to_string();
+    let mut file: File = File::create(file_path)?;
+    file.write_all(serde_json::to_string(cached_weather)?.as_bytes())?;
 
-    if saved_json.is_empty() {
-        return Err(io::Error::new(io::ErrorKind::NotFound, "No saved weather json found."));
-    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_server::serve_payloads;
+    use crate::request_weather::NwsProvider;
 
-    Ok((saved_json, json_time, json_date))
-}
\ No newline at end of file
+    const ALERTS: &str = include_str!("../../tests/fixtures/nws_alerts.json");
+
+    #[tokio::test]
+    async fn leaves_out_expired_alerts() {
+        let base_url: String = serve_payloads(&[("/alerts/active", 200, ALERTS)]);
+        let providers: Vec<Box<dyn WeatherProvider>> = vec![Box::new(NwsProvider { base_url, user_agent: "mirror-tests".to_string() })];
+        let location: Location = Location { latitude: Some(21.3069), longitude: Some(-157.8583), ..Location::default() };
+
+        // The small craft advisory ended the day before.
+        let alerts: Vec<WeatherAlert> = get_alerts(&Client::new(), &location, &providers).await;
+        let events: Vec<&str> = alerts.iter().map(|alert| alert.headline.split(" issued").next().unwrap()).collect();
+        assert_eq!(events, ["Flash Flood Warning", "Wind Advisory", "Hurricane Warning", "Special Weather Statement"]);
+    }
+}
@@ -0,0 +1,535 @@
+use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset, Local, Timelike};
+use reqwest::header::{ACCEPT, USER_AGENT};
+use reqwest::{Url, StatusCode, Client, Response};
+use serde::Deserialize;
+
+use crate::misc::{
+    WeatherError, WeatherCondition, Location, Weather, WeatherAlert, AlertSeverity, Astronomy, CurrentConditions, CurrentWeather, DailyForecast, ForecastDay, ForecastHour,
+    fahrenheit_to_celsius, compass_to_degrees
+};
+use crate::request_weather::WeatherProvider;
+
+const NWS_URL: &str = "https://api.weather.gov";
+
+pub struct NwsProvider {
+    pub base_url: String,
+    pub user_agent: String,
+}
+
+impl NwsProvider {
+    pub fn new(user_agent: &str) -> NwsProvider {
+        NwsProvider {
+            base_url: NWS_URL.to_string(),
+            user_agent: user_agent.to_string(),
+        }
+    }
+
+    async fn get_json(&self, client: &Client, url: &str) -> Result<String, WeatherError> {
+        let escaped_url: Url = match Url::parse(url) {
+            Ok(url) => url,
+            Err(url_error) => return Err(WeatherError::Network { msg: url_error.to_string() })
+        };
+
+        let nws_response: Response = match client.get(escaped_url)
+            .header(USER_AGENT, self.user_agent.as_str())
+            .header(ACCEPT, "application/geo+json")
+            .send().await {
+            Ok(resp) => resp,
+            Err(send_error) => return Err(send_error.into())
+        };
+
+        match nws_response.status() {
+            StatusCode::OK => Ok(nws_response.text().await?),
+            status_code => Err(WeatherError::HttpStatus { http_code: status_code })
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct NwsPoint {
+    properties: NwsPointProperties,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct NwsPointProperties {
+    forecast: String,
+    forecast_hourly: String,
+}
+
+/// The cached payload holds both forecasts so it can be parsed without another request.
+#[derive(Deserialize, Debug)]
+struct NwsPayload {
+    nws_hourly: NwsForecast,
+    nws_daily: NwsForecast,
+}
+
+#[derive(Deserialize, Debug)]
+struct NwsForecast {
+    properties: NwsForecastProperties,
+}
+
+#[derive(Deserialize, Debug)]
+struct NwsForecastProperties {
+    periods: Vec<NwsPeriod>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct NwsPeriod {
+    start_time: String,
+    is_daytime: bool,
+    temperature: Option<f32>,
+    temperature_unit: String,
+    #[serde(default)]
+    probability_of_precipitation: Option<NwsValue>,
+    #[serde(default)]
+    relative_humidity: Option<NwsValue>,
+    #[serde(default)]
+    wind_speed: String,
+    #[serde(default)]
+    wind_direction: String,
+    #[serde(default)]
+    icon: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct NwsValue {
+    value: Option<f32>,
+}
+
+#[derive(Deserialize, Debug)]
+struct NwsAlerts {
+    features: Vec<NwsAlertFeature>,
+}
+
+#[derive(Deserialize, Debug)]
+struct NwsAlertFeature {
+    properties: NwsAlertProperties,
+}
+
+#[derive(Deserialize, Debug)]
+struct NwsAlertProperties {
+    event: String,
+    headline: Option<String>,
+    severity: String,
+    effective: String,
+    expires: String,
+    ends: Option<String>,
+    description: Option<String>,
+}
+
+impl NwsAlertProperties {
+    fn to_alert(&self) -> Option<WeatherAlert> {
+        // `expires` is when this message lapses, `ends` is when the hazard itself is over.
+        let expires: &String = self.ends.as_ref().unwrap_or(&self.expires);
+
+        Some(WeatherAlert {
+            headline: self.headline.clone().unwrap_or_else(|| self.event.clone()),
+            severity: AlertSeverity::from_name(&self.severity),
+            effective: DateTime::parse_from_rfc3339(&self.effective).ok()?.with_timezone(&Local),
+            expires: DateTime::parse_from_rfc3339(expires).ok()?.with_timezone(&Local),
+            description: self.description.clone().unwrap_or_default(),
+        })
+    }
+}
+
+impl NwsPeriod {
+    fn start(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(self.start_time.as_str()).ok()
+    }
+
+    fn date(&self) -> Option<String> {
+        self.start().map(|start| start.format("%F").to_string())
+    }
+
+    fn temp_c(&self) -> Option<f32> {
+        match self.temperature_unit.as_str() {
+            "C" => self.temperature,
+            _ => self.temperature.map(fahrenheit_to_celsius)
+        }
+    }
+
+    /// Wind speed reads like `10 mph` or `5 to 10 mph`, the top of a range is shown.
+    fn wind_speed_kmh(&self) -> Option<f32> {
+        let wind_speed: f32 = self.wind_speed.split_whitespace()
+            .filter_map(|x| x.parse::<f32>().ok())
+            .reduce(f32::max)?;
+
+        match self.wind_speed.contains("km/h") {
+            true => Some(wind_speed),
+            false => Some(wind_speed * 1.609_344)
+        }
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for NwsProvider {
+    fn name(&self) -> &'static str {
+        "nws"
+    }
+
+    async fn fetch(&self, client: &Client, location: &Location) -> Result<String, WeatherError> {
+        let (latitude, longitude): (f64, f64) = match (location.latitude, location.longitude) {
+            (Some(latitude), Some(longitude)) => (latitude, longitude),
+            _ => return Err(WeatherError::UnknownLocation { location: format!("{} without a latitude and longitude", location.query()) })
+        };
+
+        let points_url: String = format!("{}/points/{:.4},{:.4}", self.base_url, latitude, longitude);
+        let point: NwsPoint = serde_json::from_str(self.get_json(client, &points_url).await?.as_str())?;
+
+        let hourly_json: String = self.get_json(client, &point.properties.forecast_hourly).await?;
+        let daily_json: String = self.get_json(client, &point.properties.forecast).await?;
+
+        Ok(format!("{{\"nws_hourly\":{hourly_json},\"nws_daily\":{daily_json}}}"))
+    }
+
+    fn parse(&self, payload: String, weather_time: String, weather_date: String, hour_12: bool) -> Result<Weather, WeatherError> {
+        let nws_payload: NwsPayload = serde_json::from_str(payload.as_str())?;
+        let hourly_periods: &Vec<NwsPeriod> = &nws_payload.nws_hourly.properties.periods;
+        let daily_periods: &Vec<NwsPeriod> = &nws_payload.nws_daily.properties.periods;
+
+        let current_period: &NwsPeriod = match hourly_periods.first() {
+            Some(period) => period,
+            None => return Err(WeatherError::Parse { msg: "The forecast has no days in it.".to_string() })
+        };
+
+        let current_conditions: CurrentConditions = CurrentConditions {
+            weather_code: nws_icon_to_condition(&current_period.icon).code().to_string(),
+            feels_like: current_period.temp_c(),
+            temp: current_period.temp_c(),
+            humidity: current_period.relative_humidity.as_ref().and_then(|x| x.value),
+            pressure: None,
+            uv_index: None,
+            visibility: None,
+            precip: None,
+            wind_speed: current_period.wind_speed_kmh(),
+            wind_gust: None,
+            wind_dir_degree: compass_to_degrees(&current_period.wind_direction),
+            wind_dir: current_period.wind_direction.clone(),
+            weather_desc: String::new(),
+            weather_image: String::new(),
+        };
+
+        let mut dates: Vec<String> = Vec::new();
+        for period in hourly_periods {
+            if let Some(date) = period.date() {
+                if !dates.contains(&date) {
+                    dates.push(date);
+                }
+            }
+        }
+
+        let sunrises: Vec<(String, String)> = get_daylight_changes(hourly_periods, true);
+        let sunsets: Vec<(String, String)> = get_daylight_changes(hourly_periods, false);
+
+        let mut forecast_days: Vec<ForecastDay> = Vec::new();
+        for raw_date in dates {
+            let day_periods: Vec<&NwsPeriod> = hourly_periods.iter()
+                .filter(|period| period.date().as_ref() == Some(&raw_date))
+                .collect();
+            let day_temps: Vec<f32> = day_periods.iter().filter_map(|period| period.temp_c()).collect();
+
+            let mut forecast_hours: Vec<ForecastHour> = Vec::new();
+            for period in &day_periods {
+                let hour: u32 = match period.start() {
+                    Some(start) => start.hour(),
+                    None => continue
+                };
+
+                forecast_hours.push(ForecastHour {
+                    raw_time: (hour * 100).to_string(),
+                    time: String::new(),
+                    temp: period.temp_c(),
+                    feels_like: period.temp_c(),
+                    chance_of_rain: period.probability_of_precipitation.as_ref().and_then(|x| x.value),
+                    chance_of_snow: None,
+                    precip: None,
+                    wind_speed: period.wind_speed_kmh(),
+                    wind_gust: None,
+                    wind_dir_degree: compass_to_degrees(&period.wind_direction),
+                    wind_dir: period.wind_direction.clone(),
+                    weather_code: nws_icon_to_condition(&period.icon).code().to_string(),
+                    weather_desc: String::new(),
+                    weather_image: String::new(),
+                });
+            }
+
+            if forecast_hours.is_empty() {
+                continue;
+            }
+
+            // The daily forecast splits each date into a daytime high and an overnight low.
+            let max_temp: Option<f32> = daily_periods.iter()
+                .find(|period| period.is_daytime && period.date().as_ref() == Some(&raw_date))
+                .and_then(|period| period.temp_c())
+                .or_else(|| day_temps.iter().copied().reduce(f32::max));
+            let min_temp: Option<f32> = daily_periods.iter()
+                .find(|period| !period.is_daytime && period.date().as_ref() == Some(&raw_date))
+                .and_then(|period| period.temp_c())
+                .or_else(|| day_temps.iter().copied().reduce(f32::min));
+            let avg_temp: Option<f32> = match day_temps.is_empty() {
+                true => None,
+                false => Some(day_temps.iter().sum::<f32>() / day_temps.len() as f32)
+            };
+
+            let sunrise: String = get_sun_time(&sunrises, &raw_date);
+            let sunset: String = get_sun_time(&sunsets, &raw_date);
+
+            forecast_days.push(ForecastDay {
+                raw_date,
+                date: String::new(),
+                avg_temp,
+                max_temp,
+                min_temp,
+                uv_index: None,
+                total_precip: None,
+                weather_code: String::new(),
+                weather_desc: String::new(),
+                weather_image: String::new(),
+                astronomy: vec![Astronomy {
+                    moon_phase: String::new(),
+                    moon_illumination: None,
+                    moonrise: String::new(),
+                    moonset: String::new(),
+                    sunrise,
+                    sunset,
+                }],
+                hourly: forecast_hours,
+            });
+        }
+
+        if forecast_days.is_empty() {
+            return Err(WeatherError::Parse { msg: "The forecast has no days in it.".to_string() });
+        }
+
+        Weather::new(
+            CurrentWeather { current_condition: vec![current_conditions] },
+            DailyForecast { weather: forecast_days },
+            weather_time,
+            weather_date,
+            hour_12,
+            current_period.start().map(|start| *start.offset())
+        )
+    }
+
+    async fn fetch_alerts(&self, client: &Client, location: &Location) -> Result<Vec<WeatherAlert>, WeatherError> {
+        let (latitude, longitude): (f64, f64) = match (location.latitude, location.longitude) {
+            (Some(latitude), Some(longitude)) => (latitude, longitude),
+            _ => return Ok(Vec::new())
+        };
+
+        let alerts_url: String = format!("{}/alerts/active?point={:.4},{:.4}", self.base_url, latitude, longitude);
+        let nws_alerts: NwsAlerts = serde_json::from_str(self.get_json(client, &alerts_url).await?.as_str())?;
+
+        Ok(nws_alerts.features.iter().filter_map(|feature| feature.properties.to_alert()).collect())
+    }
+}
+
+/// NWS doesn't give sun times, so they come from where the hourly periods switch between
+/// day and night. Returns the date and time of every switch to day, or to night.
+fn get_daylight_changes(hourly_periods: &[NwsPeriod], to_daytime: bool) -> Vec<(String, String)> {
+    hourly_periods.windows(2)
+        .filter(|periods| periods[0].is_daytime != to_daytime && periods[1].is_daytime == to_daytime)
+        .filter_map(|periods| periods[1].start())
+        .map(|change_start| (change_start.format("%F").to_string(), change_start.format("%I:%M %p").to_string()))
+        .collect()
+}
+
+/// Dates at the ends of the forecast may only see one of the switches, they borrow the
+/// time from the next date that has it, or the last one.
+fn get_sun_time(changes: &[(String, String)], raw_date: &str) -> String {
+    let closest_change: Option<&(String, String)> = match changes.iter().find(|(date, _)| date.as_str() >= raw_date) {
+        Some(x) => Some(x),
+        None => changes.last()
+    };
+
+    closest_change.map(|(_, time)| time.clone()).unwrap_or_default()
+}
+
+/// NWS icon urls look like `/icons/land/day/tsra_hi,40/rain,30?size=medium`,
+/// the first condition after the day or night segment is the one that's shown.
+fn nws_icon_to_condition(icon_url: &str) -> WeatherCondition {
+    let icon_path: &str = icon_url.split('?').next().unwrap_or("");
+    let icon_segments: Vec<&str> = icon_path.split('/').collect();
+    let condition: &str = match icon_segments.iter().position(|x| *x == "day" || *x == "night") {
+        Some(index) => icon_segments.get(index + 1).and_then(|x| x.split(',').next()).unwrap_or(""),
+        None => ""
+    };
+
+    match condition {
+        "skc" | "few" | "wind_skc" | "wind_few" | "hot" | "cold" => WeatherCondition::Clear,
+        "sct" | "wind_sct" => WeatherCondition::PartlyCloudy,
+        "bkn" | "wind_bkn" => WeatherCondition::Cloudy,
+        "ovc" | "wind_ovc" => WeatherCondition::Overcast,
+        "fog" => WeatherCondition::Fog,
+        "dust" | "smoke" | "haze" => WeatherCondition::Mist,
+        "snow" => WeatherCondition::ModerateSnow,
+        "blizzard" => WeatherCondition::Blizzard,
+        "rain_snow" | "rain_sleet" => WeatherCondition::LightSleet,
+        "snow_sleet" | "sleet" => WeatherCondition::ModerateOrHeavySleet,
+        "fzra" | "rain_fzra" => WeatherCondition::LightFreezingRain,
+        "snow_fzra" => WeatherCondition::ModerateOrHeavyFreezingRain,
+        "rain" => WeatherCondition::ModerateRain,
+        "rain_showers" => WeatherCondition::LightRainShower,
+        "rain_showers_hi" => WeatherCondition::PatchyRainNearby,
+        "tsra_hi" => WeatherCondition::ThunderyOutbreaksNearby,
+        "tsra_sct" => WeatherCondition::PatchyLightRainWithThunder,
+        "tsra" | "tornado" => WeatherCondition::ModerateOrHeavyRainWithThunder,
+        "hurricane" | "tropical_storm" => WeatherCondition::TorrentialRainShower,
+        _ => WeatherCondition::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_server::serve_payloads;
+
+    const POINTS: &str = include_str!("../../tests/fixtures/nws_points.json");
+    const HOURLY: &str = include_str!("../../tests/fixtures/nws_hourly.json");
+    const DAILY: &str = include_str!("../../tests/fixtures/nws_daily.json");
+    const ALERTS: &str = include_str!("../../tests/fixtures/nws_alerts.json");
+
+    fn serve_forecast() -> String {
+        serve_payloads(&[
+            ("/points/21.3069,-157.8583", 200, POINTS),
+            ("/gridpoints/HFO/153,144/forecast/hourly", 200, HOURLY),
+            ("/gridpoints/HFO/153,144/forecast", 200, DAILY),
+        ])
+    }
+
+    fn honolulu() -> Location {
+        Location { latitude: Some(21.3069), longitude: Some(-157.8583), ..Location::default() }
+    }
+
+    fn assert_close(actual: Option<f32>, expected: f32) {
+        assert!((actual.unwrap() - expected).abs() < 0.01, "{:?} isn't {}", actual, expected);
+    }
+
+    #[tokio::test]
+    async fn fetches_and_parses_a_forecast() {
+        let base_url: String = serve_forecast();
+        let provider: NwsProvider = NwsProvider { base_url, user_agent: "mirror-tests".to_string() };
+
+        let payload: String = provider.fetch(&Client::new(), &honolulu()).await.unwrap();
+        let weather: Weather = provider.parse(payload, "05:10 am".to_string(), "10/17/26".to_string(), true).unwrap();
+        assert_eq!(weather.utc_offset, FixedOffset::west_opt(10 * 3600));
+
+        let current: &CurrentConditions = &weather.current_weather.current_condition[0];
+        assert_eq!(WeatherCondition::from_code(&current.weather_code), WeatherCondition::Clear);
+        assert_close(current.temp, 23.89);
+        assert_eq!(current.humidity, Some(78.0));
+        assert_close(current.wind_speed, 8.05);
+        assert_eq!(current.wind_dir_degree, Some(67.5));
+
+        let days: &Vec<ForecastDay> = &weather.daily_forecast.weather;
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].raw_date, "2026-10-17");
+        // Highs and lows come from the daily forecast rather than the hours.
+        assert_close(days[0].max_temp, 30.0);
+        assert_close(days[0].min_temp, 23.33);
+        assert_close(days[1].max_temp, 29.44);
+    }
+
+    #[tokio::test]
+    async fn keeps_every_hourly_period() {
+        let base_url: String = serve_forecast();
+        let provider: NwsProvider = NwsProvider { base_url, user_agent: "mirror-tests".to_string() };
+
+        let payload: String = provider.fetch(&Client::new(), &honolulu()).await.unwrap();
+        let weather: Weather = provider.parse(payload, "05:10 am".to_string(), "10/17/26".to_string(), false).unwrap();
+        let days: &Vec<ForecastDay> = &weather.daily_forecast.weather;
+
+        let raw_times: Vec<&str> = days[0].hourly.iter().map(|hour| hour.raw_time.as_str()).collect();
+        assert_eq!(raw_times, (5..24).map(|hour| (hour * 100).to_string()).collect::<Vec<String>>());
+        assert_eq!(days[1].hourly.len(), 24);
+
+        let afternoon: &ForecastHour = &days[0].hourly[9];
+        assert_eq!(afternoon.raw_time, "1400");
+        assert_eq!(WeatherCondition::from_code(&afternoon.weather_code), WeatherCondition::LightRainShower);
+        assert_eq!(afternoon.chance_of_rain, Some(40.0));
+        assert_close(afternoon.temp, 28.89);
+        assert_close(afternoon.wind_speed, 16.09);
+        assert_eq!(WeatherCondition::from_code(&days[1].hourly[2].weather_code), WeatherCondition::ModerateOrHeavyRainWithThunder);
+    }
+
+    #[tokio::test]
+    async fn sun_times_come_from_the_daylight_switches() {
+        let base_url: String = serve_forecast();
+        let provider: NwsProvider = NwsProvider { base_url, user_agent: "mirror-tests".to_string() };
+
+        // 05:10 in Honolulu, whatever time that is where the tests run.
+        let fetched_at: DateTime<Local> = DateTime::parse_from_rfc3339("2026-10-17T15:10:00Z").unwrap().with_timezone(&Local);
+        let payload: String = provider.fetch(&Client::new(), &honolulu()).await.unwrap();
+        let weather: Weather = provider.parse(payload, fetched_at.format("%I:%M %P").to_string(), fetched_at.format("%D").to_string(), false).unwrap();
+        let days: &Vec<ForecastDay> = &weather.daily_forecast.weather;
+
+        assert_eq!(days[0].astronomy[0].sunrise, "06:00 AM");
+        assert_eq!(days[0].astronomy[0].sunset, "06:00 PM");
+        assert_eq!(days[1].astronomy[0].sunrise, "06:00 AM");
+
+        // It's still before sunrise there, so the current conditions and the 5 AM hour get night icons.
+        assert_eq!(weather.current_weather.current_condition[0].weather_image, WeatherCondition::Clear.image(true));
+        assert_eq!(days[0].hourly[0].raw_time, "500");
+        assert_eq!(days[0].hourly[0].weather_image, WeatherCondition::Clear.image(true));
+        assert_eq!(days[0].hourly[1].weather_image, WeatherCondition::PartlyCloudy.image(false));
+        assert_eq!(days[0].hourly[13].weather_image, WeatherCondition::Clear.image(true));
+    }
+
+    #[tokio::test]
+    async fn reads_active_alerts() {
+        let base_url: String = serve_payloads(&[("/alerts/active", 200, ALERTS)]);
+        let provider: NwsProvider = NwsProvider { base_url, user_agent: "mirror-tests".to_string() };
+
+        let alerts: Vec<WeatherAlert> = provider.fetch_alerts(&Client::new(), &honolulu()).await.unwrap();
+        let severities: Vec<(&str, AlertSeverity)> = alerts.iter()
+            .map(|alert| (alert.headline.split(" issued").next().unwrap(), alert.severity))
+            .collect();
+        // The test message has no readable start time and is left out.
+        assert_eq!(severities, [
+            ("Flash Flood Warning", AlertSeverity::Severe),
+            ("Wind Advisory", AlertSeverity::Moderate),
+            ("Hurricane Warning", AlertSeverity::Extreme),
+            ("Small Craft Advisory", AlertSeverity::Minor),
+            ("Special Weather Statement", AlertSeverity::Unknown),
+        ]);
+
+        // The advisory's message lapses in the afternoon, the wind lasts until the next evening.
+        assert_eq!(alerts[1].expires, DateTime::parse_from_rfc3339("2099-10-18T18:00:00-10:00").unwrap());
+        assert_eq!(alerts[1].effective, DateTime::parse_from_rfc3339("2026-10-17T03:30:00-10:00").unwrap());
+        // The hurricane warning has no headline or description, the event name stands in for the headline.
+        assert_eq!(alerts[2].description, "");
+        assert_eq!(alerts[0].summary(), "Radar indicated heavy rain over windward Oahu. Flash flooding is ongoing or expected to begin shortly.");
+        assert!(alerts[3].is_expired());
+    }
+
+    #[tokio::test]
+    async fn locations_need_coordinates() {
+        let provider: NwsProvider = NwsProvider::new("mirror-tests");
+        let location: Location = Location { city: "Honolulu".to_string(), ..Location::default() };
+
+        match provider.fetch(&Client::new(), &location).await {
+            Err(WeatherError::UnknownLocation { .. }) => (),
+            other => panic!("Expected an unknown location, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn icons_map_onto_conditions() {
+        let expected: [(&str, WeatherCondition); 7] = [
+            ("https://api.weather.gov/icons/land/day/skc?size=small", WeatherCondition::Clear),
+            ("https://api.weather.gov/icons/land/night/bkn?size=small", WeatherCondition::Cloudy),
+            ("https://api.weather.gov/icons/land/day/tsra_hi,40/rain,30?size=medium", WeatherCondition::ThunderyOutbreaksNearby),
+            ("https://api.weather.gov/icons/land/night/rain_showers,20?size=small", WeatherCondition::LightRainShower),
+            ("https://api.weather.gov/icons/land/day/snow_fzra?size=small", WeatherCondition::ModerateOrHeavyFreezingRain),
+            ("https://api.weather.gov/icons/land/day/volcano?size=small", WeatherCondition::Unknown),
+            ("", WeatherCondition::Unknown),
+        ];
+
+        for (icon_url, condition) in expected {
+            assert_eq!(nws_icon_to_condition(icon_url), condition, "{}", icon_url);
+        }
+    }
+}
@@ -0,0 +1,454 @@
+use async_trait::async_trait;
+use chrono::{FixedOffset, NaiveDateTime, Timelike};
+use reqwest::{Url, StatusCode, Client, Response};
+use serde::Deserialize;
+
+use crate::misc::{
+    WeatherError, WeatherCondition, Location, Weather, Astronomy, CurrentConditions, CurrentWeather, DailyForecast, ForecastDay, ForecastHour,
+    degrees_to_compass
+};
+use crate::request_weather::WeatherProvider;
+
+const OPEN_METEO_URL: &str = "https://api.open-meteo.com/v1/forecast";
+const GEOCODING_URL: &str = "https://geocoding-api.open-meteo.com/v1/search";
+const CURRENT_FIELDS: &str = "temperature_2m,relative_humidity_2m,apparent_temperature,precipitation,weather_code,surface_pressure,wind_speed_10m,wind_direction_10m,wind_gusts_10m";
+const HOURLY_FIELDS: &str = "temperature_2m,apparent_temperature,precipitation_probability,precipitation,weather_code,visibility,uv_index,wind_speed_10m,wind_direction_10m,wind_gusts_10m";
+const DAILY_FIELDS: &str = "temperature_2m_max,temperature_2m_min,precipitation_sum,uv_index_max,sunrise,sunset";
+
+pub struct OpenMeteoProvider {
+    pub base_url: String,
+    pub geocoding_url: String,
+    pub forecast_days: usize,
+}
+
+impl Default for OpenMeteoProvider {
+    fn default() -> Self {
+        OpenMeteoProvider {
+            base_url: OPEN_METEO_URL.to_string(),
+            geocoding_url: GEOCODING_URL.to_string(),
+            forecast_days: 3,
+        }
+    }
+}
+
+impl OpenMeteoProvider {
+    /// Looks up coordinates for locations that only have a city or zip code.
+    async fn geocode(&self, client: &Client, location: &Location) -> Result<(f64, f64), WeatherError> {
+        let search_name: &String = match location.city.is_empty() {
+            true => &location.zip_code,
+            false => &location.city
+        };
+        if search_name.is_empty() {
+            return Err(WeatherError::UnknownLocation { location: "a location without a city, zip_code or latitude and longitude".to_string() });
+        }
+
+        let escaped_url: Url = match Url::parse_with_params(self.geocoding_url.as_str(), &[
+            ("name", search_name.as_str()),
+            ("count", "1"),
+        ]) {
+            Ok(url) => url,
+            Err(url_error) => return Err(WeatherError::Network { msg: url_error.to_string() })
+        };
+
+        let geocoding_response: Response = match client.get(escaped_url).send().await {
+            Ok(resp) => resp,
+            Err(send_error) => return Err(send_error.into())
+        };
+        if geocoding_response.status() != StatusCode::OK {
+            return Err(WeatherError::HttpStatus { http_code: geocoding_response.status() });
+        }
+
+        let geocoding_results: GeocodingResults = serde_json::from_str(geocoding_response.text().await?.as_str())?;
+        match geocoding_results.results.first() {
+            Some(place) => Ok((place.latitude, place.longitude)),
+            None => Err(WeatherError::UnknownLocation { location: search_name.clone() })
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct GeocodingResults {
+    #[serde(default)]
+    results: Vec<GeocodingPlace>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GeocodingPlace {
+    latitude: f64,
+    longitude: f64,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenMeteoForecast {
+    #[serde(default)]
+    utc_offset_seconds: Option<i32>,
+    current: OpenMeteoCurrent,
+    hourly: OpenMeteoHourly,
+    daily: OpenMeteoDaily,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenMeteoCurrent {
+    time: String,
+    temperature_2m: Option<f32>,
+    relative_humidity_2m: Option<f32>,
+    apparent_temperature: Option<f32>,
+    precipitation: Option<f32>,
+    weather_code: Option<u8>,
+    surface_pressure: Option<f32>,
+    wind_speed_10m: Option<f32>,
+    wind_direction_10m: Option<f32>,
+    wind_gusts_10m: Option<f32>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenMeteoHourly {
+    time: Vec<String>,
+    temperature_2m: Vec<Option<f32>>,
+    apparent_temperature: Vec<Option<f32>>,
+    precipitation_probability: Vec<Option<f32>>,
+    precipitation: Vec<Option<f32>>,
+    weather_code: Vec<Option<u8>>,
+    visibility: Vec<Option<f32>>,
+    uv_index: Vec<Option<f32>>,
+    wind_speed_10m: Vec<Option<f32>>,
+    wind_direction_10m: Vec<Option<f32>>,
+    wind_gusts_10m: Vec<Option<f32>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenMeteoDaily {
+    time: Vec<String>,
+    temperature_2m_max: Vec<Option<f32>>,
+    temperature_2m_min: Vec<Option<f32>>,
+    precipitation_sum: Vec<Option<f32>>,
+    uv_index_max: Vec<Option<f32>>,
+    sunrise: Vec<String>,
+    sunset: Vec<String>,
+}
+
+#[async_trait]
+impl WeatherProvider for OpenMeteoProvider {
+    fn name(&self) -> &'static str {
+        "open-meteo"
+    }
+
+    async fn fetch(&self, client: &Client, location: &Location) -> Result<String, WeatherError> {
+        let (latitude, longitude): (f64, f64) = match (location.latitude, location.longitude) {
+            (Some(latitude), Some(longitude)) => (latitude, longitude),
+            _ => self.geocode(client, location).await?
+        };
+
+        let escaped_url: Url = match Url::parse_with_params(self.base_url.as_str(), &[
+            ("latitude", latitude.to_string()),
+            ("longitude", longitude.to_string()),
+            ("current", CURRENT_FIELDS.to_string()),
+            ("hourly", HOURLY_FIELDS.to_string()),
+            ("daily", DAILY_FIELDS.to_string()),
+            ("timezone", "auto".to_string()),
+            ("forecast_days", self.forecast_days.to_string()),
+        ]) {
+            Ok(url) => url,
+            Err(url_error) => return Err(WeatherError::Network { msg: url_error.to_string() })
+        };
+
+        let meteo_response: Response = match client.get(escaped_url).send().await {
+            Ok(resp) => resp,
+            Err(send_error) => return Err(send_error.into())
+        };
+
+        match meteo_response.status() {
+            StatusCode::OK => Ok(meteo_response.text().await?),
+            status_code => Err(WeatherError::HttpStatus { http_code: status_code })
+        }
+    }
+
+    fn parse(&self, payload: String, weather_time: String, weather_date: String, hour_12: bool) -> Result<Weather, WeatherError> {
+        let forecast: OpenMeteoForecast = serde_json::from_str(payload.as_str())?;
+        let hourly: &OpenMeteoHourly = &forecast.hourly;
+
+        // Visibility and UV index are only published hourly, so borrow them from the current hour.
+        let current_hour: Option<usize> = hourly.time.iter()
+            .position(|hour_time| hour_time.get(..13) == forecast.current.time.get(..13));
+        let hourly_value = |values: &Vec<Option<f32>>| -> Option<f32> {
+            current_hour.and_then(|index| values.get(index).copied().flatten())
+        };
+
+        let current_conditions: CurrentConditions = CurrentConditions {
+            weather_code: wmo_to_condition(forecast.current.weather_code).code().to_string(),
+            feels_like: forecast.current.apparent_temperature,
+            temp: forecast.current.temperature_2m,
+            humidity: forecast.current.relative_humidity_2m,
+            pressure: forecast.current.surface_pressure,
+            uv_index: hourly_value(&hourly.uv_index),
+            visibility: hourly_value(&hourly.visibility).map(|meters| meters / 1000.0),
+            precip: forecast.current.precipitation,
+            wind_speed: forecast.current.wind_speed_10m,
+            wind_gust: forecast.current.wind_gusts_10m,
+            wind_dir_degree: forecast.current.wind_direction_10m,
+            wind_dir: degrees_to_compass(forecast.current.wind_direction_10m),
+            weather_desc: String::new(),
+            weather_image: String::new(),
+        };
+
+        let mut forecast_days: Vec<ForecastDay> = Vec::new();
+        for (day_index, raw_date) in forecast.daily.time.iter().enumerate() {
+            let mut forecast_hours: Vec<ForecastHour> = Vec::new();
+            let mut day_temps: Vec<f32> = Vec::new();
+            for (hour_index, hour_time) in hourly.time.iter().enumerate() {
+                if !hour_time.starts_with(raw_date.as_str()) {
+                    continue;
+                }
+
+                let temp: Option<f32> = hourly.temperature_2m.get(hour_index).copied().flatten();
+                if let Some(x) = temp {
+                    day_temps.push(x);
+                }
+
+                let hour: u32 = match NaiveDateTime::parse_from_str(hour_time, "%Y-%m-%dT%H:%M") {
+                    Ok(x) => x.hour(),
+                    Err(_) => continue
+                };
+
+                let wind_dir_degree: Option<f32> = hourly.wind_direction_10m.get(hour_index).copied().flatten();
+                forecast_hours.push(ForecastHour {
+                    raw_time: (hour * 100).to_string(),
+                    time: String::new(),
+                    temp,
+                    feels_like: hourly.apparent_temperature.get(hour_index).copied().flatten(),
+                    chance_of_rain: hourly.precipitation_probability.get(hour_index).copied().flatten(),
+                    chance_of_snow: None,
+                    precip: hourly.precipitation.get(hour_index).copied().flatten(),
+                    wind_speed: hourly.wind_speed_10m.get(hour_index).copied().flatten(),
+                    wind_gust: hourly.wind_gusts_10m.get(hour_index).copied().flatten(),
+                    wind_dir_degree,
+                    wind_dir: degrees_to_compass(wind_dir_degree),
+                    weather_code: wmo_to_condition(hourly.weather_code.get(hour_index).copied().flatten()).code().to_string(),
+                    weather_desc: String::new(),
+                    weather_image: String::new(),
+                });
+            }
+
+            if forecast_hours.is_empty() {
+                continue;
+            }
+
+            let avg_temp: Option<f32> = match day_temps.is_empty() {
+                true => None,
+                false => Some(day_temps.iter().sum::<f32>() / day_temps.len() as f32)
+            };
+            forecast_days.push(ForecastDay {
+                raw_date: raw_date.clone(),
+                date: String::new(),
+                avg_temp,
+                max_temp: forecast.daily.temperature_2m_max.get(day_index).copied().flatten(),
+                min_temp: forecast.daily.temperature_2m_min.get(day_index).copied().flatten(),
+                uv_index: forecast.daily.uv_index_max.get(day_index).copied().flatten(),
+                total_precip: forecast.daily.precipitation_sum.get(day_index).copied().flatten(),
+                weather_code: String::new(),
+                weather_desc: String::new(),
+                weather_image: String::new(),
+                astronomy: vec![Astronomy {
+                    moon_phase: String::new(),
+                    moon_illumination: None,
+                    moonrise: String::new(),
+                    moonset: String::new(),
+                    sunrise: reformat_iso_time(forecast.daily.sunrise.get(day_index)),
+                    sunset: reformat_iso_time(forecast.daily.sunset.get(day_index)),
+                }],
+                hourly: forecast_hours,
+            });
+        }
+
+        if forecast_days.is_empty() {
+            return Err(WeatherError::Parse { msg: "The forecast has no days in it.".to_string() });
+        }
+
+        Weather::new(
+            CurrentWeather { current_condition: vec![current_conditions] },
+            DailyForecast { weather: forecast_days },
+            weather_time,
+            weather_date,
+            hour_12,
+            forecast.utc_offset_seconds.and_then(FixedOffset::east_opt)
+        )
+    }
+}
+
+/// Maps a WMO weather interpretation code onto the closest condition.
+fn wmo_to_condition(wmo_code: Option<u8>) -> WeatherCondition {
+    match wmo_code {
+        Some(0) | Some(1) => WeatherCondition::Clear,
+        Some(2) => WeatherCondition::PartlyCloudy,
+        Some(3) => WeatherCondition::Overcast,
+        Some(45) => WeatherCondition::Fog,
+        Some(48) => WeatherCondition::FreezingFog,
+        Some(51) => WeatherCondition::PatchyLightDrizzle,
+        Some(53) | Some(55) => WeatherCondition::LightDrizzle,
+        Some(56) => WeatherCondition::FreezingDrizzle,
+        Some(57) => WeatherCondition::HeavyFreezingDrizzle,
+        Some(61) => WeatherCondition::LightRain,
+        Some(63) => WeatherCondition::ModerateRain,
+        Some(65) => WeatherCondition::HeavyRain,
+        Some(66) => WeatherCondition::LightFreezingRain,
+        Some(67) => WeatherCondition::ModerateOrHeavyFreezingRain,
+        Some(71) | Some(77) => WeatherCondition::LightSnow,
+        Some(73) => WeatherCondition::ModerateSnow,
+        Some(75) => WeatherCondition::HeavySnow,
+        Some(80) => WeatherCondition::LightRainShower,
+        Some(81) => WeatherCondition::ModerateOrHeavyRainShower,
+        Some(82) => WeatherCondition::TorrentialRainShower,
+        Some(85) => WeatherCondition::LightSnowShowers,
+        Some(86) => WeatherCondition::ModerateOrHeavySnowShowers,
+        Some(95) => WeatherCondition::PatchyLightRainWithThunder,
+        Some(96) | Some(99) => WeatherCondition::ModerateOrHeavyRainWithThunder,
+        _ => WeatherCondition::Unknown
+    }
+}
+
+
+fn reformat_iso_time(iso_time: Option<&String>) -> String {
+    match iso_time.map(|x| NaiveDateTime::parse_from_str(x, "%Y-%m-%dT%H:%M")) {
+        Some(Ok(time)) => time.format("%I:%M %p").to_string(),
+        _ => String::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_server::serve_payloads;
+    use chrono::FixedOffset;
+
+    const FORECAST: &str = include_str!("../../tests/fixtures/open_meteo_forecast.json");
+    const GEOCODING: &str = include_str!("../../tests/fixtures/open_meteo_geocoding.json");
+    const GEOCODING_EMPTY: &str = include_str!("../../tests/fixtures/open_meteo_geocoding_empty.json");
+
+    fn test_provider(base_url: &str) -> OpenMeteoProvider {
+        OpenMeteoProvider {
+            base_url: format!("{base_url}/v1/forecast"),
+            geocoding_url: format!("{base_url}/v1/search"),
+            forecast_days: 2,
+        }
+    }
+
+    fn condition_of(weather_code: &str) -> WeatherCondition {
+        WeatherCondition::from_code(weather_code)
+    }
+
+    #[tokio::test]
+    async fn fetches_and_parses_a_forecast() {
+        let base_url: String = serve_payloads(&[("/v1/forecast", 200, FORECAST)]);
+        let provider: OpenMeteoProvider = test_provider(&base_url);
+        let location: Location = Location { latitude: Some(30.45), longitude: Some(-91.19), ..Location::default() };
+
+        let payload: String = provider.fetch(&Client::new(), &location).await.unwrap();
+        assert_eq!(payload, FORECAST);
+
+        let weather: Weather = provider.parse(payload, "02:15 pm".to_string(), "10/17/26".to_string(), true).unwrap();
+        assert_eq!(weather.utc_offset, FixedOffset::west_opt(5 * 3600));
+
+        let current: &CurrentConditions = &weather.current_weather.current_condition[0];
+        assert_eq!(condition_of(&current.weather_code), WeatherCondition::Overcast);
+        assert_eq!(current.temp, Some(24.3));
+        assert_eq!(current.humidity, Some(61.0));
+        assert_eq!(current.wind_dir, "SSW");
+        // Borrowed from the 14:00 hour, visibility converted from meters.
+        assert_eq!(current.uv_index, Some(4.2));
+        assert!((current.visibility.unwrap() - 24.14).abs() < 0.001);
+
+        let days: &Vec<ForecastDay> = &weather.daily_forecast.weather;
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].raw_date, "2026-10-17");
+        assert_eq!(days[0].max_temp, Some(24.4));
+        assert_eq!(days[0].min_temp, Some(18.1));
+        assert_eq!(days[0].total_precip, Some(4.8));
+        assert_eq!(days[0].astronomy[0].sunrise, "07:08 AM");
+        assert_eq!(days[0].astronomy[0].sunset, "06:31 PM");
+    }
+
+    #[tokio::test]
+    async fn maps_hourly_wmo_codes_onto_conditions() {
+        let base_url: String = serve_payloads(&[("/v1/forecast", 200, FORECAST)]);
+        let provider: OpenMeteoProvider = test_provider(&base_url);
+        let location: Location = Location { latitude: Some(30.45), longitude: Some(-91.19), ..Location::default() };
+
+        let payload: String = provider.fetch(&Client::new(), &location).await.unwrap();
+        let weather: Weather = provider.parse(payload, "02:15 pm".to_string(), "10/17/26".to_string(), false).unwrap();
+        let first_day: &Vec<ForecastHour> = &weather.daily_forecast.weather[0].hourly;
+
+        // Every hour is kept, like the National Weather Service's forecast.
+        let raw_times: Vec<String> = first_day.iter().map(|hour| hour.raw_time.clone()).collect();
+        let every_hour: Vec<String> = (0..24).map(|hour| (hour * 100).to_string()).collect();
+        assert_eq!(raw_times, every_hour);
+
+        // The recorded day changes its weather every three hours.
+        let conditions: Vec<WeatherCondition> = first_day.iter().step_by(3).map(|hour| condition_of(&hour.weather_code)).collect();
+        assert_eq!(conditions, [
+            WeatherCondition::Clear,
+            WeatherCondition::LightRain,
+            WeatherCondition::PartlyCloudy,
+            WeatherCondition::Overcast,
+            WeatherCondition::PatchyLightRainWithThunder,
+            WeatherCondition::LightRainShower,
+            WeatherCondition::Fog,
+            WeatherCondition::LightSnow,
+        ]);
+        assert_eq!(condition_of(&first_day[4].weather_code), WeatherCondition::LightRain);
+
+        // 3 AM is before the 7:08 sunrise and noon is after it.
+        assert_eq!(first_day[3].weather_image, WeatherCondition::LightRain.image(true));
+        assert_eq!(first_day[12].weather_image, WeatherCondition::PatchyLightRainWithThunder.image(false));
+        assert_eq!(condition_of(&weather.daily_forecast.weather[1].hourly[12].weather_code), WeatherCondition::HeavyRain);
+    }
+
+    #[tokio::test]
+    async fn geocodes_locations_without_coordinates() {
+        let base_url: String = serve_payloads(&[("/v1/search", 200, GEOCODING), ("/v1/forecast", 200, FORECAST)]);
+        let provider: OpenMeteoProvider = test_provider(&base_url);
+        let location: Location = Location { city: "Baton Rouge".to_string(), ..Location::default() };
+
+        assert_eq!(provider.geocode(&Client::new(), &location).await.unwrap(), (30.45075, -91.15455));
+        assert_eq!(provider.fetch(&Client::new(), &location).await.unwrap(), FORECAST);
+    }
+
+    #[tokio::test]
+    async fn unknown_places_and_server_errors_are_reported() {
+        let base_url: String = serve_payloads(&[("/v1/search", 200, GEOCODING_EMPTY), ("/v1/forecast", 503, "")]);
+        let provider: OpenMeteoProvider = test_provider(&base_url);
+
+        let atlantis: Location = Location { city: "Atlantis".to_string(), ..Location::default() };
+        match provider.fetch(&Client::new(), &atlantis).await {
+            Err(WeatherError::UnknownLocation { location }) => assert_eq!(location, "Atlantis"),
+            other => panic!("Expected an unknown location, got {:?}", other)
+        }
+
+        let home: Location = Location { latitude: Some(30.45), longitude: Some(-91.19), ..Location::default() };
+        match provider.fetch(&Client::new(), &home).await {
+            Err(WeatherError::HttpStatus { http_code }) => assert_eq!(http_code, StatusCode::SERVICE_UNAVAILABLE),
+            other => panic!("Expected an http status error, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn wmo_codes_map_to_the_closest_condition() {
+        let expected: [(Option<u8>, WeatherCondition); 12] = [
+            (Some(0), WeatherCondition::Clear),
+            (Some(2), WeatherCondition::PartlyCloudy),
+            (Some(48), WeatherCondition::FreezingFog),
+            (Some(57), WeatherCondition::HeavyFreezingDrizzle),
+            (Some(65), WeatherCondition::HeavyRain),
+            (Some(67), WeatherCondition::ModerateOrHeavyFreezingRain),
+            (Some(77), WeatherCondition::LightSnow),
+            (Some(82), WeatherCondition::TorrentialRainShower),
+            (Some(86), WeatherCondition::ModerateOrHeavySnowShowers),
+            (Some(99), WeatherCondition::ModerateOrHeavyRainWithThunder),
+            (Some(4), WeatherCondition::Unknown),
+            (None, WeatherCondition::Unknown),
+        ];
+
+        for (wmo_code, condition) in expected {
+            assert_eq!(wmo_to_condition(wmo_code), condition, "WMO code {:?}", wmo_code);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+use reqwest::Client;
+
+use crate::misc::{Location, UserSettings, Weather, WeatherAlert, WeatherError};
+use crate::request_weather::{NwsProvider, OpenMeteoProvider, WttrProvider};
+
+const PROVIDER_NAMES: [&str; 3] = ["wttr", "open-meteo", "nws"];
+
+/// A source of weather data. `fetch` returns the provider's raw payload so it can be
+/// cached as-is, and `parse` turns that payload into the shared `Weather` model.
+#[async_trait]
+pub trait WeatherProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn fetch(&self, client: &Client, location: &Location) -> Result<String, WeatherError>;
+
+    fn parse(&self, payload: String, weather_time: String, weather_date: String, hour_12: bool) -> Result<Weather, WeatherError>;
+
+    /// Active alerts for the location. Providers without an alerts feed have none.
+    async fn fetch_alerts(&self, _client: &Client, _location: &Location) -> Result<Vec<WeatherAlert>, WeatherError> {
+        Ok(Vec::new())
+    }
+}
+
+pub fn is_provider_name(provider_name: &str) -> bool {
+    PROVIDER_NAMES.contains(&provider_name.to_lowercase().as_str())
+}
+
+/// Builds the providers named in settings.json, in the order they should be tried.
+pub fn get_providers(user_settings: &UserSettings) -> Vec<Box<dyn WeatherProvider>> {
+    let mut providers: Vec<Box<dyn WeatherProvider>> = Vec::new();
+    for provider_name in &user_settings.providers {
+        match provider_name.to_lowercase().as_str() {
+            "wttr" => providers.push(Box::new(WttrProvider)),
+            "open-meteo" => providers.push(Box::new(OpenMeteoProvider {
+                forecast_days: user_settings.forecast_days,
+                ..OpenMeteoProvider::default()
+            })),
+            "nws" => providers.push(Box::new(NwsProvider::new(&user_settings.nws_user_agent))),
+            _ => println!("Unknown weather provider: {provider_name}")
+        }
+    }
+
+    if providers.is_empty() {
+        providers.push(Box::new(WttrProvider));
+    }
+
+    providers
+}
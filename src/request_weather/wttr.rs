@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use chrono::{FixedOffset, NaiveDateTime, NaiveTime};
+use reqwest::{Url, StatusCode, Client, Response};
+use serde::Deserialize;
+
+use crate::misc::{WeatherError, Location, Weather, CurrentWeather, DailyForecast};
+use crate::request_weather::WeatherProvider;
+
+pub struct WttrProvider;
+
+#[derive(Deserialize, Debug)]
+struct WttrObservation {
+    current_condition: Vec<WttrObservationTime>,
+}
+
+#[derive(Deserialize, Debug)]
+struct WttrObservationTime {
+    observation_time: String,
+    #[serde(rename = "localObsDateTime")]
+    local_obs_date_time: String,
+}
+
+#[async_trait]
+impl WeatherProvider for WttrProvider {
+    fn name(&self) -> &'static str {
+        "wttr"
+    }
+
+    async fn fetch(&self, client: &Client, location: &Location) -> Result<String, WeatherError> {
+        let raw_url: String = format!("http://wttr.in/{}?format=j1", location.query());
+        let escaped_url: Url = match Url::parse(raw_url.as_str()){
+            Ok(url) => url,
+            Err(url_error) => return Err(WeatherError::Network { msg: url_error.to_string() })
+        };
+
+        let wttr_response: Response = match client.get(escaped_url).send().await {
+            Ok(resp) => resp,
+            Err(send_error) => return Err(send_error.into())
+        };
+
+        match wttr_response.status() {
+            StatusCode::OK => {
+                let weather_json: String = wttr_response.text().await?;
+                if weather_json.contains("Unknown location; please try") {
+                    return Err(WeatherError::UnknownLocation { location: location.query() });
+                }
+
+                Ok(weather_json)
+            },
+            status_code => Err(WeatherError::HttpStatus { http_code: status_code })
+        }
+    }
+
+    fn parse(&self, payload: String, weather_time: String, weather_date: String, hour_12: bool) -> Result<Weather, WeatherError> {
+        let current_weather: CurrentWeather = serde_json::from_str(payload.as_str())?;
+        let mut daily_forecast: DailyForecast = serde_json::from_str(payload.as_str())?;
+
+        // wttr.in has no daily rain total, add up the three hour buckets instead.
+        for day in daily_forecast.weather.iter_mut() {
+            let hourly_precip: Vec<f32> = day.hourly.iter().filter_map(|hour| hour.precip).collect();
+            if !hourly_precip.is_empty() {
+                day.total_precip = Some(hourly_precip.iter().sum());
+            }
+        }
+
+        let utc_offset: Option<FixedOffset> = get_utc_offset(payload.as_str());
+        Weather::new(current_weather, daily_forecast, weather_time, weather_date, hour_12, utc_offset)
+    }
+}
+
+/// wttr.in gives the observation time in UTC and at the location but no timezone, so work it out from the two.
+fn get_utc_offset(payload: &str) -> Option<FixedOffset> {
+    let observation: WttrObservation = serde_json::from_str(payload).ok()?;
+    let observation_time: &WttrObservationTime = observation.current_condition.first()?;
+    let local_time: NaiveDateTime = NaiveDateTime::parse_from_str(&observation_time.local_obs_date_time, "%Y-%m-%d %I:%M %p").ok()?;
+    let utc_time: NaiveTime = NaiveTime::parse_from_str(&observation_time.observation_time, "%I:%M %p").ok()?;
+
+    // Only the time of day is in UTC, so a difference past a real offset has wrapped around midnight.
+    let mut offset_minutes: i64 = (local_time.time() - utc_time).num_minutes();
+    if offset_minutes < -12 * 60 {
+        offset_minutes += 24 * 60;
+    } else if offset_minutes > 14 * 60 {
+        offset_minutes -= 24 * 60;
+    }
+
+    // Offsets are whole quarter hours.
+    let offset_quarters: i64 = (offset_minutes as f64 / 15.0).round() as i64;
+    FixedOffset::east_opt((offset_quarters * 15 * 60) as i32)
+}
@@ -1,2 +1,16 @@
-pub use self::gui::{CurrentWeatherGui, Message, WeatherGui, WeatherGuiStatus, DaysWeatherGui, HourlyWeatherGui, WeatherShim};
+pub use self::gui::{Message, WeatherGui};
+pub use self::calendar::{CalendarMessage, CalendarModule};
+pub use self::chart::ForecastChart;
+pub use self::clock::{ClockMessage, ClockModule};
+pub use self::module::{MirrorModule, get_modules, is_module_name};
+pub use self::news::{NewsMessage, NewsModule};
+pub use self::sky::SkyWidget;
+pub use self::weather::{WeatherModule, WeatherMessage};
+mod calendar;
+mod chart;
+mod clock;
 mod gui;
+mod module;
+mod news;
+mod sky;
+mod weather;
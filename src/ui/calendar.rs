@@ -0,0 +1,147 @@
+use iced::widget::{row, text, Column};
+use iced::{Alignment, Command, Element, Length, Subscription};
+use chrono::{Local, NaiveDate};
+
+use crate::calendar::{EventOccurrence, get_events};
+use crate::misc::{FeedError, FontSizes, Language, UserSettings};
+use crate::ui::{Message, MirrorModule};
+
+const REFRESH_SECS: u64 = 900;
+
+/// Upcoming events from the calendars in settings, grouped by day.
+pub struct CalendarModule {
+    pub calendars: Vec<String>,
+    pub max_events: usize,
+    pub days: i64,
+    pub hour_12: bool,
+    pub language: Language,
+    pub fonts: FontSizes,
+    pub events: Option<Vec<EventOccurrence>>,
+    pub update_error: Option<FeedError>,
+}
+
+#[derive(Debug, Clone)]
+pub enum CalendarMessage {
+    Refresh,
+    EventsUpdated(Result<Vec<EventOccurrence>, FeedError>),
+}
+
+impl CalendarModule {
+    pub fn new(settings: &UserSettings) -> CalendarModule {
+        CalendarModule {
+            calendars: settings.calendars.clone(),
+            max_events: settings.calendar_events,
+            days: settings.calendar_days,
+            hour_12: settings.hour_12,
+            language: settings.language,
+            fonts: settings.layout.font_sizes,
+            events: None,
+            update_error: None,
+        }
+    }
+
+    fn refresh(&self) -> Command<Message> {
+        let calendars: Vec<String> = self.calendars.clone();
+        let (max_events, days): (usize, i64) = (self.max_events, self.days);
+        Command::perform(
+            async move { get_events(&calendars, max_events, days).await },
+            |x| Message::Calendar(CalendarMessage::EventsUpdated(x))
+        )
+    }
+
+    fn view_event(&self, event: &EventOccurrence) -> Element<'_, Message> {
+        let time_format: &str = match self.hour_12 {
+            true => "%I:%M %p",
+            false => "%H:%M"
+        };
+        // Events that are already underway show when they finish instead.
+        let event_time: String = match (event.all_day, event.start <= Local::now()) {
+            (true, _) => self.language.translate("All day"),
+            (false, true) => format!("{} {}", self.language.translate("Until"), event.end.format(time_format)),
+            (false, false) => event.start.format(time_format).to_string()
+        };
+
+        row![
+            text(event_time).size(self.fonts.small).width(Length::Fixed(120.0)),
+            text(&event.summary).size(self.fonts.small),
+        ].spacing(10)
+        .align_items(Alignment::Center)
+        .into()
+    }
+}
+
+impl MirrorModule for CalendarModule {
+    fn name(&self) -> &'static str {
+        "calendar"
+    }
+
+    fn init(&mut self) -> Command<Message> {
+        self.refresh()
+    }
+
+    fn update(&mut self, message: &Message) -> Command<Message> {
+        let calendar_message: &CalendarMessage = match message {
+            Message::Calendar(x) => x,
+            _ => return Command::none()
+        };
+
+        match calendar_message {
+            CalendarMessage::Refresh => self.refresh(),
+
+            CalendarMessage::EventsUpdated(Ok(events)) => {
+                self.events = Some(events.clone());
+                self.update_error = None;
+                Command::none()
+            },
+
+            // Keep showing the last events, they're still mostly right.
+            CalendarMessage::EventsUpdated(Err(calendar_error)) => {
+                self.update_error = Some(calendar_error.clone());
+                Command::none()
+            },
+        }
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        iced::time::every(std::time::Duration::from_secs(REFRESH_SECS)).map(|_| {Message::Calendar(CalendarMessage::Refresh)})
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let events: &Vec<EventOccurrence> = match (&self.events, &self.update_error) {
+            (Some(x), _) => x,
+            (None, Some(calendar_error)) => return text(calendar_error).size(self.fonts.small).into(),
+            (None, None) => return text(self.language.translate("Updating Calendar...")).size(self.fonts.body).into()
+        };
+
+        if events.is_empty() {
+            return text(self.language.translate("No upcoming events")).size(self.fonts.body).into();
+        }
+
+        // Events that started before today, like a trip that's underway, are listed under today.
+        let today: NaiveDate = Local::now().date_naive();
+        let mut days: Vec<(NaiveDate, Vec<&EventOccurrence>)> = Vec::new();
+        for event in events {
+            let event_day: NaiveDate = event.start.date_naive().max(today);
+            match days.last_mut() {
+                Some((day, day_events)) if *day == event_day => day_events.push(event),
+                _ => days.push((event_day, vec![event]))
+            }
+        }
+
+        let mut day_groups: Vec<Element<Message>> = Vec::new();
+        for (day, day_events) in days {
+            let mut day_lines: Vec<Element<Message>> = vec![
+                text(self.language.format_date(day, self.language.long_date_format())).size(self.fonts.body).into()
+            ];
+            day_lines.extend(day_events.into_iter().map(|event| self.view_event(event)));
+
+            day_groups.push(Column::with_children(day_lines).spacing(5).into());
+        }
+
+        Column::with_children(day_groups)
+            .spacing(15)
+            .width(Length::Fill)
+            .align_items(Alignment::Start)
+            .into()
+    }
+}
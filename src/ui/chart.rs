@@ -0,0 +1,187 @@
+use iced::widget::canvas::{self, Frame, Geometry, Path, Stroke, Text};
+use iced::{Point, Rectangle, Size, Theme};
+use iced::alignment::{Horizontal, Vertical};
+
+use crate::misc::{ForecastHour, Language, UnitSystem};
+use crate::ui::Message;
+
+const LABEL_SIZE: f32 = 18.0;
+const AXIS_WIDTH: f32 = 50.0;
+const LABEL_HEIGHT: f32 = 25.0;
+
+#[derive(Debug, Clone)]
+pub struct ChartPoint {
+    pub time: String,
+    pub temp: Option<f32>,
+    pub feels_like: Option<f32>,
+    pub chance_of_precip: Option<f32>,
+}
+
+/// Temperature and feels like lines over precipitation chance bars.
+/// Temperatures are already converted to the display unit.
+#[derive(Debug, Clone, Default)]
+pub struct ForecastChart {
+    pub points: Vec<ChartPoint>,
+    pub temperature_symbol: String,
+    pub temperature_label: String,
+    pub feels_like_label: String,
+    pub precip_label: String,
+}
+
+impl ForecastChart {
+    pub fn new(hours: &[&ForecastHour], units: &UnitSystem, language: &Language) -> ForecastChart {
+        ForecastChart {
+            points: hours.iter().map(|hour| ChartPoint {
+                time: hour.time.clone(),
+                temp: hour.temp.map(|x| units.convert_temperature(x)),
+                feels_like: hour.feels_like.map(|x| units.convert_temperature(x)),
+                chance_of_precip: hour.chance_of_precip(),
+            }).collect(),
+            temperature_symbol: units.temperature_symbol().to_string(),
+            temperature_label: language.translate("Temperature"),
+            feels_like_label: language.translate("Feels like"),
+            precip_label: language.translate("Precip %"),
+        }
+    }
+
+    fn temperature_range(&self) -> Option<(f32, f32)> {
+        let temps: Vec<f32> = self.points.iter()
+            .flat_map(|point| [point.temp, point.feels_like])
+            .flatten()
+            .collect();
+
+        let min_temp: f32 = temps.iter().copied().reduce(f32::min)?;
+        let max_temp: f32 = temps.iter().copied().reduce(f32::max)?;
+
+        // Keep a flat day from drawing as a line along the top edge.
+        match max_temp - min_temp < 1.0 {
+            true => Some((min_temp - 1.0, max_temp + 1.0)),
+            false => Some((min_temp, max_temp))
+        }
+    }
+
+    fn draw_line(&self, frame: &mut Frame, plot: &Rectangle, range: (f32, f32), value: fn(&ChartPoint) -> Option<f32>, stroke: Stroke) {
+        let path: Path = Path::new(|builder| {
+            let mut drawing: bool = false;
+            for (index, point) in self.points.iter().enumerate() {
+                match value(point) {
+                    Some(x) => {
+                        let position: Point = Point::new(x_position(plot, index, self.points.len()), y_position(plot, range, x));
+                        match drawing {
+                            true => builder.line_to(position),
+                            false => builder.move_to(position)
+                        };
+                        drawing = true;
+                    },
+                    // Missing readings leave a gap instead of dropping to zero.
+                    None => drawing = false
+                }
+            }
+        });
+
+        frame.stroke(&path, stroke);
+    }
+}
+
+impl canvas::Program<Message> for ForecastChart {
+    type State = ();
+
+    fn draw(&self, _state: &Self::State, theme: &Theme, bounds: Rectangle, _cursor: canvas::Cursor) -> Vec<Geometry> {
+        let mut frame: Frame = Frame::new(bounds.size());
+        let palette: iced::theme::Palette = theme.palette();
+
+        let (min_temp, max_temp): (f32, f32) = match self.temperature_range() {
+            Some(x) => x,
+            None => return vec![frame.into_geometry()]
+        };
+
+        let chart_height: f32 = bounds.height - LABEL_HEIGHT;
+        let line_plot: Rectangle = Rectangle::new(
+            Point::new(AXIS_WIDTH, LABEL_SIZE),
+            Size::new(bounds.width - AXIS_WIDTH, chart_height * 0.7 - LABEL_SIZE)
+        );
+        let bar_plot: Rectangle = Rectangle::new(
+            Point::new(AXIS_WIDTH, chart_height * 0.75),
+            Size::new(bounds.width - AXIS_WIDTH, chart_height * 0.25)
+        );
+
+        let bar_width: f32 = (bar_plot.width / self.points.len() as f32 * 0.6).max(1.0);
+        for (index, point) in self.points.iter().enumerate() {
+            let chance_of_precip: f32 = point.chance_of_precip.unwrap_or(0.0).clamp(0.0, 100.0);
+            let bar_height: f32 = bar_plot.height * chance_of_precip / 100.0;
+            let bar_x: f32 = x_position(&bar_plot, index, self.points.len()) - bar_width / 2.0;
+
+            frame.fill(
+                &Path::rectangle(Point::new(bar_x, bar_plot.y + bar_plot.height - bar_height), Size::new(bar_width, bar_height)),
+                palette.primary
+            );
+        }
+
+        self.draw_line(&mut frame, &line_plot, (min_temp, max_temp), |point| point.feels_like,
+            Stroke::default().with_color(palette.primary).with_width(2.0));
+        self.draw_line(&mut frame, &line_plot, (min_temp, max_temp), |point| point.temp,
+            Stroke::default().with_color(palette.text).with_width(3.0));
+
+        for (label, temp) in [(format!("{:.0}{}", max_temp, self.temperature_symbol), max_temp), (format!("{:.0}{}", min_temp, self.temperature_symbol), min_temp)] {
+            frame.fill_text(Text {
+                content: label,
+                position: Point::new(AXIS_WIDTH - 8.0, y_position(&line_plot, (min_temp, max_temp), temp)),
+                color: palette.text,
+                size: LABEL_SIZE,
+                horizontal_alignment: Horizontal::Right,
+                vertical_alignment: Vertical::Center,
+                ..Text::default()
+            });
+        }
+
+        frame.fill_text(Text {
+            content: self.precip_label.clone(),
+            position: Point::new(AXIS_WIDTH - 8.0, bar_plot.y + bar_plot.height / 2.0),
+            color: palette.primary,
+            size: LABEL_SIZE * 0.8,
+            horizontal_alignment: Horizontal::Right,
+            vertical_alignment: Vertical::Center,
+            ..Text::default()
+        });
+
+        // Roughly one time label every 80 pixels so they don't run together.
+        let label_every: usize = ((self.points.len() as f32 * 80.0 / line_plot.width).ceil() as usize).max(1);
+        for (index, point) in self.points.iter().enumerate().step_by(label_every) {
+            frame.fill_text(Text {
+                content: point.time.clone(),
+                position: Point::new(x_position(&line_plot, index, self.points.len()), bounds.height - LABEL_HEIGHT / 2.0),
+                color: palette.text,
+                size: LABEL_SIZE,
+                horizontal_alignment: Horizontal::Center,
+                vertical_alignment: Vertical::Center,
+                ..Text::default()
+            });
+        }
+
+        // Legend colored to match the lines.
+        for (label, color, right_edge) in [(&self.feels_like_label, palette.primary, bounds.width), (&self.temperature_label, palette.text, bounds.width - 100.0)] {
+            frame.fill_text(Text {
+                content: label.clone(),
+                position: Point::new(right_edge, 0.0),
+                color,
+                size: LABEL_SIZE * 0.8,
+                horizontal_alignment: Horizontal::Right,
+                vertical_alignment: Vertical::Top,
+                ..Text::default()
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// Points are spread evenly with half a step of margin on each side.
+fn x_position(plot: &Rectangle, index: usize, point_count: usize) -> f32 {
+    let step: f32 = plot.width / point_count.max(1) as f32;
+    plot.x + step * (index as f32 + 0.5)
+}
+
+fn y_position(plot: &Rectangle, (min_temp, max_temp): (f32, f32), temp: f32) -> f32 {
+    plot.y + plot.height * (1.0 - (temp - min_temp) / (max_temp - min_temp))
+}
+
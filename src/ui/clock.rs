@@ -0,0 +1,183 @@
+use iced::widget::{column, text, Column, Row};
+use iced::{Alignment, Command, Element, Subscription};
+use chrono::{DateTime, Local, Timelike};
+use chrono_tz::Tz;
+
+use crate::misc::{FontSizes, Language, UserSettings, WorldClock};
+use crate::ui::{Message, MirrorModule};
+
+/// The time and date, with any world clocks underneath.
+pub struct ClockModule {
+    pub clock_format: String,
+    pub language: Language,
+    pub fonts: FontSizes,
+    pub world_clocks: Vec<(String, Tz)>,
+    pub clock: String,
+    pub date: String,
+    pub world_times: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ClockMessage {
+    Tick,
+}
+
+impl ClockModule {
+    pub fn new(settings: &UserSettings) -> ClockModule {
+        let mut clock_module: ClockModule = ClockModule {
+            clock_format: get_clock_format(settings),
+            language: settings.language,
+            fonts: settings.layout.font_sizes,
+            world_clocks: get_world_clocks(&settings.world_clocks),
+            clock: "".to_string(),
+            date: "".to_string(),
+            world_times: Vec::new(),
+        };
+        clock_module.tick();
+
+        clock_module
+    }
+
+    fn tick(&mut self) {
+        let now: DateTime<Local> = Local::now();
+        self.clock = now.format(&self.clock_format).to_string();
+        self.date = get_date(&now, &self.language);
+        self.world_times = self.world_clocks.iter()
+            .map(|(name, time_zone)| (name.clone(), get_world_time(&now, time_zone, &self.clock_format, &self.language)))
+            .collect();
+    }
+
+    /// Ticks are chained rather than on a fixed interval so each one lands just after the clock changes.
+    fn schedule_tick(&self) -> Command<Message> {
+        Command::perform(wait_for_next_tick(shows_seconds(&self.clock_format)), |_| Message::Clock(ClockMessage::Tick))
+    }
+}
+
+impl MirrorModule for ClockModule {
+    fn name(&self) -> &'static str {
+        "clock"
+    }
+
+    fn init(&mut self) -> Command<Message> {
+        self.schedule_tick()
+    }
+
+    fn update(&mut self, message: &Message) -> Command<Message> {
+        match message {
+            Message::Clock(ClockMessage::Tick) => {
+                self.tick();
+                self.schedule_tick()
+            },
+            _ => Command::none()
+        }
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        Subscription::none()
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let world_row: Row<Message> = Row::with_children(
+            self.world_times.iter().map(|(name, time)| {
+                column![
+                    text(name).size(self.fonts.small),
+                    text(time).size(self.fonts.small),
+                ].align_items(Alignment::Center)
+                .into()
+            }).collect()
+        ).spacing(30);
+
+        let mut clock_lines: Vec<Element<Message>> = vec![
+            text(&self.clock).size(self.fonts.title).into(),
+            text(&self.date).size(self.fonts.small).into(),
+        ];
+        if !self.world_times.is_empty() {
+            clock_lines.push(world_row.into());
+        }
+
+        Column::with_children(clock_lines)
+            .spacing(5)
+            .align_items(Alignment::Center)
+            .into()
+    }
+}
+
+/// `clock_format` from settings, otherwise built from `hour_12` and `clock_seconds`.
+fn get_clock_format(settings: &UserSettings) -> String {
+    if !settings.clock_format.is_empty() {
+        return settings.clock_format.clone();
+    }
+
+    match (settings.hour_12, settings.clock_seconds) {
+        (true, true) => "%I:%M:%S %p".to_string(),
+        (true, false) => "%I:%M %p".to_string(),
+        (false, true) => "%H:%M:%S".to_string(),
+        (false, false) => "%H:%M".to_string()
+    }
+}
+
+/// Time zones are checked when settings are loaded, anything unreadable here is left out.
+fn get_world_clocks(world_clocks: &[WorldClock]) -> Vec<(String, Tz)> {
+    world_clocks.iter()
+        .filter_map(|world_clock| match world_clock.time_zone.parse::<Tz>() {
+            Ok(time_zone) => Some((world_clock.name.clone(), time_zone)),
+            Err(_) => None
+        })
+        .collect()
+}
+
+fn get_date(now: &DateTime<Local>, language: &Language) -> String {
+    language.format_date(now.date_naive(), language.long_date_format())
+}
+
+/// Shows the weekday too when it's already tomorrow, or still yesterday, there.
+fn get_world_time(now: &DateTime<Local>, time_zone: &Tz, clock_format: &str, language: &Language) -> String {
+    let zone_time: DateTime<Tz> = now.with_timezone(time_zone);
+    let time: String = zone_time.format(clock_format).to_string();
+
+    match zone_time.date_naive() == now.date_naive() {
+        true => time,
+        false => format!("{} {}", time, language.format_date(zone_time.date_naive(), "%a"))
+    }
+}
+
+/// Renders the format twice within one minute, a second and a half apart. If anything changed,
+/// like `%S`, `%-S`, `%.f` or the seconds inside `%r` and `%X`, the clock ticks every second.
+fn shows_seconds(clock_format: &str) -> bool {
+    let now: DateTime<Local> = Local::now();
+    let first: Option<DateTime<Local>> = now.with_second(10).and_then(|x| x.with_nanosecond(0));
+    let second: Option<DateTime<Local>> = now.with_second(11).and_then(|x| x.with_nanosecond(500_000_000));
+
+    match (first, second) {
+        (Some(first), Some(second)) => first.format(clock_format).to_string() != second.format(clock_format).to_string(),
+        _ => false
+    }
+}
+
+async fn wait_for_next_tick(seconds: bool) {
+    let now: DateTime<Local> = Local::now();
+    // Leap seconds can push the milliseconds past 999.
+    let millis: u64 = (now.timestamp_subsec_millis() as u64).min(999);
+    let wait_millis: u64 = match seconds {
+        true => 1000 - millis,
+        false => 60_000 - (now.second() as u64 * 1000 + millis)
+    };
+
+    // A few extra milliseconds so the tick never lands just before the change.
+    tokio::time::sleep(std::time::Duration::from_millis(wait_millis + 5)).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_seconds_in_any_format() {
+        for format in ["%S", "%-S", "%H:%M:%S", "%T", "%r", "%X", "%c", "%s", "%H:%M %.3f", "%H:%M %f"] {
+            assert!(shows_seconds(format), "{} shows seconds", format);
+        }
+        for format in ["%H:%M", "%I:%M %p", "%-I:%M %P %Z", "%a %R", "%x"] {
+            assert!(!shows_seconds(format), "{} doesn't show seconds", format);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use iced::{Command, Element, Subscription};
+
+use crate::misc::UserSettings;
+use crate::ui::{CalendarModule, ClockModule, Message, NewsModule, WeatherModule};
+
+const MODULE_NAMES: [&str; 4] = ["calendar", "clock", "news", "weather"];
+
+/// Something that can be placed in a region of the mirror. Each module keeps its own state
+/// and message type wrapped in `Message`, every module sees every message and ignores the
+/// ones that aren't its own.
+pub trait MirrorModule {
+    /// The name used for the module in the layout.
+    fn name(&self) -> &'static str;
+
+    /// Work to start as soon as the mirror opens, like the first weather request.
+    fn init(&mut self) -> Command<Message> {
+        Command::none()
+    }
+
+    fn update(&mut self, message: &Message) -> Command<Message>;
+
+    fn subscription(&self) -> Subscription<Message>;
+
+    fn view(&self) -> Element<'_, Message>;
+
+    /// Shown across the top of the mirror above every region, wherever the module is placed.
+    fn view_banner(&self) -> Option<Element<'_, Message>> {
+        None
+    }
+}
+
+pub fn is_module_name(module_name: &str) -> bool {
+    MODULE_NAMES.contains(&module_name.to_lowercase().as_str())
+}
+
+/// Builds one of each module placed in the layout.
+pub fn get_modules(user_settings: &UserSettings) -> Vec<Box<dyn MirrorModule>> {
+    let mut modules: Vec<Box<dyn MirrorModule>> = Vec::new();
+    for module_name in user_settings.layout.module_names() {
+        if modules.iter().any(|module| module.name() == module_name.to_lowercase()) {
+            continue;
+        }
+
+        match module_name.to_lowercase().as_str() {
+            "calendar" => modules.push(Box::new(CalendarModule::new(user_settings))),
+            "clock" => modules.push(Box::new(ClockModule::new(user_settings))),
+            "news" => modules.push(Box::new(NewsModule::new(user_settings))),
+            "weather" => modules.push(Box::new(WeatherModule::new(user_settings))),
+            _ => println!("Unknown module: {module_name}")
+        }
+    }
+
+    modules
+}
@@ -0,0 +1,141 @@
+use iced::widget::{column, text};
+use iced::{Alignment, Command, Element, Length, Subscription};
+use chrono::{DateTime, Local};
+
+use crate::misc::{FeedError, FontSizes, Language, NewsFeed, UserSettings, get_current_dir};
+use crate::news::{Headline, get_news};
+use crate::ui::{Message, MirrorModule};
+
+const REFRESH_SECS: u64 = 900;
+
+/// Headlines from the feeds in settings, one at a time.
+pub struct NewsModule {
+    pub feeds: Vec<NewsFeed>,
+    pub max_headlines: usize,
+    pub interval_secs: u64,
+    pub language: Language,
+    pub fonts: FontSizes,
+    pub headlines: Option<Vec<Headline>>,
+    pub current: usize,
+    pub update_error: Option<FeedError>,
+}
+
+#[derive(Debug, Clone)]
+pub enum NewsMessage {
+    Refresh,
+    Rotate,
+    NewsUpdated(Result<Vec<Headline>, FeedError>),
+}
+
+impl NewsModule {
+    pub fn new(settings: &UserSettings) -> NewsModule {
+        NewsModule {
+            feeds: settings.news_feeds.clone(),
+            max_headlines: settings.news_headlines,
+            interval_secs: settings.news_interval_secs,
+            language: settings.language,
+            fonts: settings.layout.font_sizes,
+            headlines: None,
+            current: 0,
+            update_error: None,
+        }
+    }
+
+    fn refresh(&self) -> Command<Message> {
+        let feeds: Vec<NewsFeed> = self.feeds.clone();
+        let max_headlines: usize = self.max_headlines;
+        Command::perform(
+            async move { get_news(&feeds, max_headlines, &get_current_dir()).await },
+            |x| Message::News(NewsMessage::NewsUpdated(x))
+        )
+    }
+}
+
+impl MirrorModule for NewsModule {
+    fn name(&self) -> &'static str {
+        "news"
+    }
+
+    fn init(&mut self) -> Command<Message> {
+        self.refresh()
+    }
+
+    fn update(&mut self, message: &Message) -> Command<Message> {
+        let news_message: &NewsMessage = match message {
+            Message::News(x) => x,
+            _ => return Command::none()
+        };
+
+        match news_message {
+            NewsMessage::Refresh => self.refresh(),
+
+            NewsMessage::Rotate => {
+                if let Some(headlines) = &self.headlines {
+                    self.current = (self.current + 1) % headlines.len().max(1);
+                }
+                Command::none()
+            },
+
+            NewsMessage::NewsUpdated(Ok(headlines)) => {
+                if self.current >= headlines.len() {
+                    self.current = 0;
+                }
+                self.headlines = Some(headlines.clone());
+                self.update_error = None;
+                Command::none()
+            },
+
+            // Keep rotating through the headlines already loaded.
+            NewsMessage::NewsUpdated(Err(news_error)) => {
+                self.update_error = Some(news_error.clone());
+                Command::none()
+            },
+        }
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        let refresh_update: Subscription<Message> = iced::time::every(std::time::Duration::from_secs(REFRESH_SECS)).map(|_| {Message::News(NewsMessage::Refresh)});
+
+        let rotate_update: Subscription<Message> = match &self.headlines {
+            Some(headlines) if headlines.len() > 1 => iced::time::every(std::time::Duration::from_secs(self.interval_secs)).map(|_| {Message::News(NewsMessage::Rotate)}),
+            _ => Subscription::none()
+        };
+
+        Subscription::batch([refresh_update, rotate_update])
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let headline: &Headline = match (&self.headlines, &self.update_error) {
+            (Some(headlines), _) if !headlines.is_empty() => &headlines[self.current.min(headlines.len() - 1)],
+            (Some(_), _) => return text(self.language.translate("No headlines")).size(self.fonts.body).into(),
+            (None, Some(news_error)) => return text(news_error).size(self.fonts.small).into(),
+            (None, None) => return text(self.language.translate("Updating News...")).size(self.fonts.body).into()
+        };
+
+        let byline: String = match &headline.published {
+            Some(published) => format!("{} · {}", headline.source, get_headline_age(published, self.language)),
+            None => headline.source.clone()
+        };
+
+        column![
+            text(&headline.title).size(self.fonts.body),
+            text(byline).size(self.fonts.small),
+        ].spacing(5)
+        .width(Length::Fill)
+        .align_items(Alignment::Center)
+        .into()
+    }
+}
+
+fn get_headline_age(published: &DateTime<Local>, language: Language) -> String {
+    let age: chrono::Duration = Local::now() - *published;
+    match (age.num_days(), age.num_hours(), age.num_minutes()) {
+        (0, 0, minutes) if minutes < 1 => language.translate("just now"),
+        (0, 0, 1) => language.translate("1 minute ago"),
+        (0, 0, minutes) => language.translate("{} minutes ago").replace("{}", &minutes.to_string()),
+        (0, 1, _) => language.translate("1 hour ago"),
+        (0, hours, _) => language.translate("{} hours ago").replace("{}", &hours.to_string()),
+        (1, _, _) => language.translate("1 day ago"),
+        (days, _, _) => language.translate("{} days ago").replace("{}", &days.to_string())
+    }
+}
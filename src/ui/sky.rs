@@ -0,0 +1,204 @@
+use chrono::{DateTime, FixedOffset, Local, NaiveTime};
+use iced::widget::canvas::{self, Frame, Geometry, Path, Stroke, Text};
+use iced::alignment::{Horizontal, Vertical};
+use iced::{Color, Point, Rectangle, Size, Theme};
+use std::f32::consts::PI;
+
+use crate::misc::{Astronomy, Language, moon_phase_fraction, moon_illumination, moon_phase_name, parse_astronomy_time};
+use crate::ui::Message;
+
+const LABEL_SIZE: f32 = 18.0;
+const CURVE_STEPS: usize = 48;
+
+/// Today's daylight arc with the sun's position on it, and the moon's phase.
+#[derive(Debug, Clone, Default)]
+pub struct SkyWidget {
+    pub sunrise: Option<NaiveTime>,
+    pub sunset: Option<NaiveTime>,
+    pub sunrise_label: String,
+    pub sunset_label: String,
+    pub moonrise_label: String,
+    pub moonset_label: String,
+    pub moon_phase: String,
+    pub moon_illumination: Option<f32>,
+    pub moon_phase_fraction: f32,
+    pub utc_offset: Option<FixedOffset>,
+    pub now: NaiveTime,
+}
+
+impl SkyWidget {
+    pub fn new(astronomy: &Astronomy, hour_12: bool, utc_offset: Option<FixedOffset>, language: &Language) -> SkyWidget {
+        let sunrise: Option<NaiveTime> = parse_astronomy_time(&astronomy.sunrise);
+        let sunset: Option<NaiveTime> = parse_astronomy_time(&astronomy.sunset);
+
+        let mut sky_widget: SkyWidget = SkyWidget {
+            sunrise,
+            sunset,
+            sunrise_label: format_astronomy_time(&astronomy.sunrise, hour_12),
+            sunset_label: format_astronomy_time(&astronomy.sunset, hour_12),
+            moonrise_label: format_astronomy_time(&astronomy.moonrise, hour_12),
+            moonset_label: format_astronomy_time(&astronomy.moonset, hour_12),
+            moon_phase: astronomy.moon_phase.clone(),
+            moon_illumination: astronomy.moon_illumination,
+            moon_phase_fraction: 0.0,
+            utc_offset,
+            now: Local::now().time(),
+        };
+        sky_widget.tick();
+
+        // Providers without moon data get it worked out from the date.
+        if sky_widget.moon_phase.is_empty() {
+            sky_widget.moon_phase = moon_phase_name(sky_widget.moon_phase_fraction).to_string();
+        }
+        sky_widget.moon_phase = language.translate(&sky_widget.moon_phase);
+        if sky_widget.moon_illumination.is_none() {
+            sky_widget.moon_illumination = Some(moon_illumination(sky_widget.moon_phase_fraction));
+        }
+
+        sky_widget
+    }
+
+    /// Sun times are for the forecast location, so the sun is placed using the time there.
+    pub fn tick(&mut self) {
+        let now: DateTime<Local> = Local::now();
+        self.now = match self.utc_offset {
+            Some(offset) => now.with_timezone(&offset).time(),
+            None => now.time()
+        };
+        self.moon_phase_fraction = moon_phase_fraction(&now);
+    }
+
+    /// 0 at sunrise and 1 at sunset, `None` at night or without sun times.
+    fn daylight_fraction(&self) -> Option<f32> {
+        let (sunrise, sunset): (NaiveTime, NaiveTime) = (self.sunrise?, self.sunset?);
+        let day_length: f32 = (sunset - sunrise).num_seconds() as f32;
+        if day_length <= 0.0 {
+            return None;
+        }
+
+        let fraction: f32 = (self.now - sunrise).num_seconds() as f32 / day_length;
+        match (0.0..=1.0).contains(&fraction) {
+            true => Some(fraction),
+            false => None
+        }
+    }
+
+    fn draw_sun(&self, frame: &mut Frame, bounds: &Rectangle, palette: &iced::theme::Palette) {
+        let horizon: f32 = bounds.y + bounds.height - LABEL_SIZE * 1.5;
+        let center: Point = Point::new(bounds.x + bounds.width / 2.0, horizon);
+        let radius: f32 = (bounds.width / 2.0 - LABEL_SIZE * 2.0).min(horizon - bounds.y - 12.0).max(1.0);
+
+        let daylight_arc: Path = Path::new(|builder| {
+            for step in 0..=CURVE_STEPS {
+                let angle: f32 = PI + PI * step as f32 / CURVE_STEPS as f32;
+                let position: Point = Point::new(center.x + radius * angle.cos(), center.y + radius * angle.sin());
+                match step {
+                    0 => builder.move_to(position),
+                    _ => builder.line_to(position)
+                };
+            }
+        });
+        frame.stroke(&daylight_arc, Stroke::default().with_color(palette.primary).with_width(2.0));
+        frame.stroke(
+            &Path::line(Point::new(bounds.x, horizon), Point::new(bounds.x + bounds.width, horizon)),
+            Stroke::default().with_color(palette.primary).with_width(1.0)
+        );
+
+        if let Some(fraction) = self.daylight_fraction() {
+            let angle: f32 = PI + PI * fraction;
+            frame.fill(
+                &Path::circle(Point::new(center.x + radius * angle.cos(), center.y + radius * angle.sin()), 10.0),
+                palette.text
+            );
+        }
+
+        for (label, x) in [(&self.sunrise_label, center.x - radius), (&self.sunset_label, center.x + radius)] {
+            frame.fill_text(Text {
+                content: label.clone(),
+                position: Point::new(x, horizon + 4.0),
+                color: palette.text,
+                size: LABEL_SIZE,
+                horizontal_alignment: Horizontal::Center,
+                vertical_alignment: Vertical::Top,
+                ..Text::default()
+            });
+        }
+    }
+
+    fn draw_moon(&self, frame: &mut Frame, bounds: &Rectangle, palette: &iced::theme::Palette) {
+        let center: Point = Point::new(bounds.x + bounds.width / 2.0, bounds.y + (bounds.height - LABEL_SIZE * 3.0) / 2.0);
+        let radius: f32 = (bounds.width / 2.0 - 10.0).min((bounds.height - LABEL_SIZE * 3.0) / 2.0 - 4.0).max(1.0);
+
+        frame.fill(&Path::circle(center, radius), Color { a: 0.35, ..palette.primary });
+
+        // The lit side follows the limb, the terminator is the same curve squashed by the phase.
+        let lit_side: f32 = match self.moon_phase_fraction < 0.5 {
+            true => 1.0,
+            false => -1.0
+        };
+        let terminator_scale: f32 = (self.moon_phase_fraction * 2.0 * PI).cos();
+        let lit_area: Path = Path::new(|builder| {
+            for step in 0..=CURVE_STEPS {
+                let angle: f32 = -PI / 2.0 + PI * step as f32 / CURVE_STEPS as f32;
+                let position: Point = Point::new(center.x + lit_side * radius * angle.cos(), center.y + radius * angle.sin());
+                match step {
+                    0 => builder.move_to(position),
+                    _ => builder.line_to(position)
+                };
+            }
+            for step in (0..=CURVE_STEPS).rev() {
+                let angle: f32 = -PI / 2.0 + PI * step as f32 / CURVE_STEPS as f32;
+                builder.line_to(Point::new(
+                    center.x + lit_side * radius * angle.cos() * terminator_scale,
+                    center.y + radius * angle.sin()
+                ));
+            }
+            builder.close();
+        });
+        frame.fill(&lit_area, palette.text);
+
+        let illumination: String = match self.moon_illumination {
+            Some(x) => format!("{:.0}%", x),
+            None => "--".to_string()
+        };
+        let moon_times: String = match (self.moonrise_label.is_empty(), self.moonset_label.is_empty()) {
+            (false, false) => format!("{} - {}", self.moonrise_label, self.moonset_label),
+            _ => "".to_string()
+        };
+
+        for (line, label) in [format!("{} {}", self.moon_phase, illumination), moon_times].into_iter().enumerate() {
+            frame.fill_text(Text {
+                content: label,
+                position: Point::new(center.x, center.y + radius + 6.0 + line as f32 * LABEL_SIZE * 1.2),
+                color: palette.text,
+                size: LABEL_SIZE,
+                horizontal_alignment: Horizontal::Center,
+                vertical_alignment: Vertical::Top,
+                ..Text::default()
+            });
+        }
+    }
+}
+
+impl canvas::Program<Message> for SkyWidget {
+    type State = ();
+
+    fn draw(&self, _state: &Self::State, theme: &Theme, bounds: Rectangle, _cursor: canvas::Cursor) -> Vec<Geometry> {
+        let mut frame: Frame = Frame::new(bounds.size());
+        let palette: iced::theme::Palette = theme.palette();
+
+        let sun_width: f32 = bounds.width * 0.6;
+        self.draw_sun(&mut frame, &Rectangle::new(Point::ORIGIN, Size::new(sun_width, bounds.height)), &palette);
+        self.draw_moon(&mut frame, &Rectangle::new(Point::new(sun_width, 0.0), Size::new(bounds.width - sun_width, bounds.height)), &palette);
+
+        vec![frame.into_geometry()]
+    }
+}
+
+fn format_astronomy_time(astronomy_time: &str, hour_12: bool) -> String {
+    match (parse_astronomy_time(astronomy_time), hour_12) {
+        (Some(x), true) => x.format("%I:%M %p").to_string(),
+        (Some(x), false) => x.format("%H:%M").to_string(),
+        (None, _) => "".to_string()
+    }
+}
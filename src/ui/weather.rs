@@ -0,0 +1,827 @@
+use iced::{Renderer, theme};
+use iced::widget::{column, container, row, text, button, svg, horizontal_rule, vertical_rule, Canvas, Column, Row};
+use iced::{Alignment, Background, Command, Element, Length, Theme, Subscription};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, Utc};
+use rand::Rng;
+use std::sync::Arc;
+
+use crate::misc::{Weather, WeatherAlert, AlertSeverity, UserSettings, HourlyDisplay, FontSizes, Language, Layout, Location, UnitSystem, ForecastDay, ForecastHour, UIError, format_measurement};
+use crate::request_weather::{WeatherProvider, get_weather, get_providers};
+use crate::ui::{ForecastChart, SkyWidget, Message, MirrorModule};
+
+const RETRY_BASE_SECS: u64 = 30;
+const RETRY_MAX_SECS: u64 = 900;
+const MAX_RETRIES: u32 = 6;
+const HOURS_PER_ROW: usize = 3;
+const DETAILED_DAYS: usize = 3;
+
+/// Current conditions, hourly and daily forecasts, alerts and the other locations.
+pub struct WeatherModule {
+    pub status: WeatherGuiStatus,
+    pub settings: UserSettings,
+    pub providers: Arc<Vec<Box<dyn WeatherProvider>>>,
+    pub weather_state: WeatherShim,
+    pub update_error: Option<UIError>,
+    pub retry_count: u32,
+    pub next_retry: Option<DateTime<Local>>,
+}   
+
+#[derive(Debug, Clone)]
+pub enum WeatherGuiStatus {
+    Loading,
+    Loaded { weather: Box<WeatherShim> },
+    Errored { error: UIError },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WeatherShim {
+    pub current_weather: CurrentWeatherGui,
+    pub daily_weather: Vec<DaysWeatherGui>,
+    pub hourly_weather: Vec<HourlyWeatherGui>,
+    pub chart: ForecastChart,
+    pub sky: SkyWidget,
+    pub alerts: Vec<WeatherAlert>,
+    pub location_name: String,
+    pub cached_at: Option<DateTime<Local>>,
+    pub other_locations: Vec<LocationWeatherGui>,
+}
+
+#[derive(Debug, Clone)]
+pub enum WeatherMessage {
+    Update,
+    Retry,
+    Tick,
+    WeatherUpdated(Result<Box<WeatherShim>, UIError>),
+}
+
+#[derive(Debug, Clone)]
+pub struct CurrentWeatherGui {
+    pub current_temp: String,
+    pub feels_like: String,
+    pub humidity: String,
+    pub pressure: String,
+    pub uv_index: String,
+    pub visibility: String,
+    pub wind: String,
+    pub wind_dir: String,
+    pub wind_arrow: svg::Handle,
+    pub weather_desc: String,
+    pub weather_image: svg::Handle,
+}
+
+impl Default for CurrentWeatherGui {
+    fn default() -> Self {
+        CurrentWeatherGui { 
+            current_temp: "".to_string(),
+            feels_like: "".to_string(),
+            humidity: "".to_string(),
+            pressure: "".to_string(),
+            uv_index: "".to_string(),
+            visibility: "".to_string(),
+            wind: "".to_string(),
+            wind_dir: "".to_string(),
+            wind_arrow: get_wind_arrow(None),
+            weather_desc: "Error".to_string(),
+            weather_image: svg::Handle::from_path("svg\\wi-na.svg")
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DaysWeatherGui {
+    pub date: String,
+    pub short_date: String,
+    pub max_temp: String,
+    pub min_temp: String,
+    pub sunrise: String,
+    pub sunset: String,
+    pub average_chance_of_precip: String,
+    pub total_precip: String,
+    pub weather_desc: String,
+    pub weather_image: svg::Handle,
+}
+
+#[derive(Debug, Clone)]
+pub struct LocationWeatherGui {
+    pub name: String,
+    pub current_temp: String,
+    pub max_temp: String,
+    pub min_temp: String,
+    pub weather_desc: String,
+    pub weather_image: svg::Handle,
+}
+
+#[derive(Debug, Clone)]
+pub struct HourlyWeatherGui  {
+    pub time: String,
+    pub temp: String,
+    pub feels_like: String,
+    pub chance_of_precip: String,
+    pub precip: String,
+    pub wind: String,
+    pub wind_arrow: svg::Handle,
+    pub weather_desc: String,
+    pub weather_image: svg::Handle,
+} 
+
+impl WeatherModule {
+    pub fn new(settings: &UserSettings) -> WeatherModule {
+        WeatherModule {
+            settings: settings.clone(),
+            providers: Arc::new(get_providers(settings)),
+            status: WeatherGuiStatus::Loading,
+            weather_state: WeatherShim::default(),
+            update_error: None,
+            retry_count: 0,
+            next_retry: None,
+        }
+    }
+
+    fn refresh(&self) -> Command<Message> {
+        Command::perform(
+            update_all_weather(self.settings.clone(), self.providers.clone()),
+            |x| Message::Weather(WeatherMessage::WeatherUpdated(x.map(Box::new)))
+        )
+    }
+
+    fn schedule_retry(&mut self) {
+        if self.retry_count >= MAX_RETRIES {
+            println!("Giving up on retries until the next scheduled update.");
+            self.next_retry = None;
+            return;
+        }
+
+        self.next_retry = Some(Local::now() + get_retry_delay(self.retry_count));
+        self.retry_count += 1;
+    }
+}
+
+impl MirrorModule for WeatherModule {
+    fn name(&self) -> &'static str {
+        "weather"
+    }
+
+    fn init(&mut self) -> Command<Message> {
+        self.refresh()
+    }
+
+    fn update(&mut self, message: &Message) -> Command<Message> {
+        let weather_message: &WeatherMessage = match message {
+            Message::Weather(x) => x,
+            _ => return Command::none()
+        };
+
+        match weather_message {
+            WeatherMessage::WeatherUpdated(Ok(weather)) => {
+                // Cached data means every provider failed, keep trying for fresh data.
+                match weather.cached_at {
+                    Some(_) => self.schedule_retry(),
+                    None => {
+                        self.retry_count = 0;
+                        self.next_retry = None;
+                    }
+                };
+                self.update_error = None;
+                self.weather_state = *weather.clone();
+                self.status = WeatherGuiStatus::Loaded { weather: weather.clone() };
+                Command::none()
+            },
+
+            WeatherMessage::WeatherUpdated(Err(weather_error)) => {
+                self.schedule_retry();
+
+                // Once something has loaded, keep it up and mention the failure instead of taking over the screen.
+                match self.status {
+                    WeatherGuiStatus::Loaded { .. } => self.update_error = Some(weather_error.clone()),
+                    _ => self.status = WeatherGuiStatus::Errored { error: weather_error.clone() }
+                };
+                Command::none()
+            },
+
+            // A scheduled update or the retry button starts the backoff over.
+            WeatherMessage::Update => {
+                self.retry_count = 0;
+                self.next_retry = None;
+                self.refresh()
+            },
+
+            WeatherMessage::Retry => {
+                match self.next_retry {
+                    Some(next_retry) if next_retry <= Local::now() => {
+                        self.next_retry = None;
+                        self.refresh()
+                    },
+                    _ => Command::none()
+                }
+            },
+
+            WeatherMessage::Tick => {
+                if let WeatherGuiStatus::Loaded { .. } = self.status {
+                    self.weather_state.sky.tick();
+                    self.weather_state.alerts.retain(|alert| !alert.is_expired());
+                    self.status = WeatherGuiStatus::Loaded { weather: Box::new(self.weather_state.clone()) };
+                }
+                Command::none()
+            },
+        }
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        let timer_update: Subscription<Message> = iced::time::every(std::time::Duration::from_secs(1800)).map(|_| {Message::Weather(WeatherMessage::Update)});
+        let sky_update: Subscription<Message> = iced::time::every(std::time::Duration::from_secs(60)).map(|_| {Message::Weather(WeatherMessage::Tick)});
+
+        let retry_update: Subscription<Message> = match self.next_retry {
+            Some(_) => iced::time::every(std::time::Duration::from_secs(1)).map(|_| {Message::Weather(WeatherMessage::Retry)}),
+            None => Subscription::none()
+        };
+
+        Subscription::batch([timer_update, sky_update, retry_update])
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let language: Language = self.settings.language;
+        let layout: &Layout = &self.settings.layout;
+        let fonts: FontSizes = layout.font_sizes;
+        let content = match self.status.clone() {
+            WeatherGuiStatus::Loading => {
+                column![text(language.translate("Updating Weather...")).size(fonts.title),].width(Length::Shrink)
+            },
+
+            WeatherGuiStatus::Loaded {weather} => {
+                let mut hourly_rows: Vec<Element<Message>> = Vec::new();
+                for hour_row in weather.hourly_weather.chunks(HOURS_PER_ROW) {
+                    if !hourly_rows.is_empty() {
+                        hourly_rows.push(horizontal_rule(layout.rule_width).into());
+                    }
+
+                    hourly_rows.push(Row::with_children(hour_row.iter().map(|hour| hour.clone().view(language, fonts)).collect())
+                        .spacing(layout.spacing)
+                        .align_items(Alignment::Center)
+                        .height(Length::FillPortion(1))
+                        .into());
+                }
+
+                let mut daily_cards: Vec<Element<Message>> = Vec::new();
+                for day in weather.daily_weather.iter().take(DETAILED_DAYS) {
+                    if !daily_cards.is_empty() {
+                        daily_cards.push(horizontal_rule(layout.rule_width).into());
+                    }
+
+                    daily_cards.push(day.clone().view(language, fonts));
+                }
+                daily_cards.push(horizontal_rule(layout.rule_width).into());
+                daily_cards.push(Canvas::new(weather.sky.clone())
+                    .width(Length::Fill)
+                    .height(Length::Fixed(170.0))
+                    .into());
+
+                let mut week_row: Vec<Element<Message>> = Vec::new();
+                if weather.daily_weather.len() > DETAILED_DAYS {
+                    week_row.push(horizontal_rule(layout.rule_width).into());
+                    week_row.push(Row::with_children(
+                        weather.daily_weather.iter().skip(DETAILED_DAYS).map(|day| day.clone().view_compact(fonts)).collect()
+                    ).spacing(layout.spacing)
+                    .into());
+                }
+
+                let hourly_block: Element<Message> = match self.settings.hourly_display {
+                    HourlyDisplay::Cards => Column::with_children(hourly_rows)
+                        .spacing(layout.spacing)
+                        .height(Length::FillPortion(layout.hourly_height))
+                        .into(),
+                    HourlyDisplay::Chart => Canvas::new(weather.chart.clone())
+                        .width(Length::Fill)
+                        .height(Length::FillPortion(layout.hourly_height))
+                        .into()
+                };
+
+                let left_side = column![
+                    row![
+                        weather.current_weather.view(language, fonts, self.settings.hour_12),
+                    ].align_items(Alignment::Center)
+                    .height(Length::FillPortion(layout.current_height)),
+                    horizontal_rule(layout.rule_width),
+                    hourly_block,
+                ].max_width(layout.column_max_width)
+                .spacing(layout.spacing)
+                .align_items(Alignment::Center);
+
+                let right_side = Column::with_children(daily_cards)
+                .max_width(layout.column_max_width)
+                .spacing(layout.spacing)
+                .align_items(Alignment::Start);
+
+                let cache_badge: Column<Message> = Column::with_children(
+                    weather.cached_at.iter().map(|cached_at| text(get_cache_age(cached_at, language)).size(fonts.small).into()).collect()
+                );
+
+                let update_status: String = match &self.update_error {
+                    Some(update_error) => format!("{}: {}", language.translate("Couldn't update"), update_error),
+                    None => "".to_string()
+                };
+                let status_line = row![
+                    text(update_status).size(fonts.small),
+                    text(get_retry_status(&self.next_retry, self.settings.hour_12, language)).size(fonts.small),
+                ].spacing(layout.spacing);
+
+                let location_cards: Row<Message> = Row::with_children(
+                    weather.other_locations.into_iter().map(|location| location.view(language, fonts)).collect()
+                ).spacing(layout.spacing);
+
+                column![
+                    text(&weather.location_name).size(fonts.body),
+                    cache_badge,
+
+                    horizontal_rule(layout.rule_width),
+
+                    row![
+                        left_side,
+                        vertical_rule(layout.rule_width),
+                        right_side,
+                    ],
+
+                    Column::with_children(week_row)
+                        .spacing(layout.spacing)
+                        .align_items(Alignment::Center),
+
+                    location_cards,
+                    status_line,
+                ].align_items(Alignment::Center)
+            },
+
+            WeatherGuiStatus::Errored { error } => {
+                let (error_string, error_advice): (String, String) = match error {
+                    UIError::APIError { error } => (error.to_string(), error.advice()),
+                    UIError::DataError { msg } => (msg, "".to_string())
+                };
+
+                column![
+                    text(language.translate("Whoops! Something went wrong...")).size(fonts.title),
+                    text(error_string).size(fonts.body),
+                    text(error_advice).size(fonts.body),
+                    text(get_retry_status(&self.next_retry, self.settings.hour_12, language)).size(fonts.small),
+                    button(text(language.translate("Try again"))).on_press(Message::Weather(WeatherMessage::Update))
+                ]
+                .spacing(layout.spacing)
+                .align_items(Alignment::Center)
+            }
+        };
+
+        container(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    }
+
+    /// Alerts go above everything else so they're seen first.
+    fn view_banner(&self) -> Option<Element<'_, Message>> {
+        let alerts: &Vec<WeatherAlert> = match &self.status {
+            WeatherGuiStatus::Loaded { weather } if !weather.alerts.is_empty() => &weather.alerts,
+            _ => return None
+        };
+
+        Some(Column::with_children(
+            alerts.iter().map(|alert| view_alert(alert, self.settings.hour_12, self.settings.language, &self.settings.layout)).collect()
+        ).spacing(self.settings.layout.spacing)
+        .width(Length::Fill)
+        .into())
+    }
+}
+
+
+impl CurrentWeatherGui {
+    fn view<'a>(self, language: Language, fonts: FontSizes, hour_12: bool) -> Element<'a, Message> {
+        let time_format: &str = match hour_12 {
+            true => "%I:%M %p",
+            false => "%H:%M"
+        };
+
+        let weather_svg: iced::widget::Svg<Renderer> = svg(self.weather_image.clone());
+            //.width(200) //100
+            //.height(200);//Length::FillPortion(2)) //100
+            //.content_fit(ContentFit::Contain);
+
+        let current_row = row![
+            column![
+                weather_svg.height(Length::FillPortion(3)),
+                text(&self.weather_desc).size(fonts.body),
+                text(format!("{}    {} {}", self.current_temp, language.translate("Feels like"), self.feels_like)).size(fonts.body),
+                text(format!("{}: {}%", language.translate("Humidity"), self.humidity)).size(fonts.body),
+                text(format!("{}: {}", language.translate("Pressure"), self.pressure)).size(fonts.body),
+                row![
+                    svg(self.wind_arrow.clone()).width(Length::Fixed(30.0)).height(Length::Fixed(30.0)),
+                    text(format!("{}: {} {}", language.translate("Wind"), self.wind, self.wind_dir)).size(fonts.body),
+                ].spacing(10)
+                .align_items(Alignment::Center),
+                text(format!("{}: {}", language.translate("Visibility"), self.visibility)).size(fonts.body), //text(format!("Visibility: {}    UV Index: {}", self.visibility, self.uv_index)).size(fonts.body),
+                text(format!("{}: {}", language.translate("UV Index"), self.uv_index)).size(fonts.body),
+                text(format!("{}: {}", language.translate("Time"), Local::now().format_localized(time_format, language.locale()))).size(fonts.body),
+            ].spacing(10)
+            .align_items(Alignment::Center)
+        ];
+
+        container(current_row)
+            .width(Length::Fill)
+            .height(Length::Shrink)
+            .center_x()
+            .center_y()
+            .into()
+    }
+
+    async fn update(weather_forecast: &Weather, units: &UnitSystem, language: &Language) -> Result<CurrentWeatherGui, UIError> {
+        let current_conditions = match weather_forecast.current_weather.current_condition.first() {
+            Some(x) => x,
+            None => {return Err(UIError::DataError {msg: "No current conditions.".to_string()});}
+        };
+
+        Ok(CurrentWeatherGui {
+            current_temp: units.temperature(current_conditions.temp),
+            feels_like: units.temperature(current_conditions.feels_like),
+            humidity: format_measurement(current_conditions.humidity),
+            pressure: units.pressure(current_conditions.pressure),
+            visibility: units.visibility(current_conditions.visibility),
+            uv_index: format_measurement(current_conditions.uv_index),
+            wind: get_wind(current_conditions.wind_speed, current_conditions.wind_gust, units, language),
+            wind_dir: current_conditions.wind_dir.clone(),
+            wind_arrow: get_wind_arrow(current_conditions.wind_dir_degree),
+            weather_desc: language.translate(&current_conditions.weather_desc),
+            weather_image: svg::Handle::from_path(current_conditions.weather_image.clone()),
+        })
+    }
+}
+
+impl DaysWeatherGui {
+    fn view<'a>(self, language: Language, fonts: FontSizes) -> Element<'a, Message> {
+        let svg: iced::widget::Svg<Renderer> = svg(self.weather_image.clone());
+            //.width(100)
+            //.height(100);
+
+        let day_row = row![
+            column![
+                svg.height(Length::FillPortion(4)),
+                text(self.date).size(fonts.body).height(Length::Fill),
+                text(self.weather_desc).size(fonts.body).height(Length::Fill),
+                text(format!("{}: {}    {}: {}", language.translate("High"), self.max_temp, language.translate("Low"), self.min_temp)).size(fonts.body).height(Length::Fill),
+                text(format!("{}% {}", self.average_chance_of_precip, language.translate("chance of precipitation"))).size(fonts.body).height(Length::Fill),
+                text(format!("{}: {}", language.translate("Precipitation"), self.total_precip)).size(fonts.body).height(Length::Fill),
+                text(format!("{}: {}    {}: {}", language.translate("Sunrise"), self.sunrise, language.translate("Sunset"), self.sunset)).size(fonts.body).height(Length::Fill),
+            ].spacing(10)
+            .align_items(Alignment::Center),
+        ];
+
+        container(day_row)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    }
+
+    /// Smaller card for the week row past the detailed days.
+    fn view_compact<'a>(self, fonts: FontSizes) -> Element<'a, Message> {
+        let svg: iced::widget::Svg<Renderer> = svg(self.weather_image.clone());
+
+        let day_column = column![
+            text(self.short_date).size(fonts.small),
+            svg.width(Length::Fixed(60.0)).height(Length::Fixed(60.0)),
+            text(format!("{} / {}", self.max_temp, self.min_temp)).size(fonts.small),
+            text(format!("{}%", self.average_chance_of_precip)).size(fonts.small),
+        ].spacing(5)
+        .align_items(Alignment::Center);
+
+        container(day_column)
+            .width(Length::Shrink)
+            .height(Length::Shrink)
+            .center_x()
+            .center_y()
+            .into()
+    }
+
+    async fn update(day_weather: &ForecastDay, units: &UnitSystem, language: &Language) -> Result<DaysWeatherGui, UIError> {
+        let (sunrise, sunset): (String, String) = match day_weather.astronomy.first() {
+            Some(astronomy) => (astronomy.sunrise.clone(), astronomy.sunset.clone()),
+            None => ("".to_string(), "".to_string())
+        };
+
+        let (date, short_date): (String, String) = match NaiveDate::parse_from_str(day_weather.raw_date.as_str(), "%F") {
+            Ok(x) => (language.format_date(x, language.long_date_format()), language.format_date(x, "%a %e")),
+            Err(_) => (day_weather.date.to_string(), day_weather.date.to_string())
+        };
+
+        Ok(DaysWeatherGui {
+            date,
+            short_date,
+            max_temp: units.temperature(day_weather.max_temp),
+            min_temp: units.temperature(day_weather.min_temp),
+            sunrise,
+            sunset,
+            average_chance_of_precip: format_measurement(get_average_precip(&day_weather.hourly)),
+            total_precip: units.precipitation(day_weather.total_precip),
+            weather_desc: language.translate(&day_weather.weather_desc),
+            weather_image: svg::Handle::from_path(&day_weather.weather_image),
+        })
+    }
+}
+
+impl LocationWeatherGui {
+    fn view<'a>(self, language: Language, fonts: FontSizes) -> Element<'a, Message> {
+        let svg: iced::widget::Svg<Renderer> = svg(self.weather_image.clone());
+
+        let location_row = row![
+            svg.width(Length::Fixed(80.0)).height(Length::Fixed(80.0)),
+            column![
+                text(&self.name).size(fonts.small),
+                text(format!("{}    {}", self.current_temp, self.weather_desc)).size(fonts.small),
+                text(format!("{}: {}    {}: {}", language.translate("High"), self.max_temp, language.translate("Low"), self.min_temp)).size(fonts.small),
+            ].spacing(5)
+        ].spacing(10)
+        .align_items(Alignment::Center);
+
+        container(location_row)
+            .width(Length::Shrink)
+            .height(Length::Shrink)
+            .center_x()
+            .center_y()
+            .into()
+    }
+
+    async fn update(location: &Location, weather_forecast: &Weather, units: &UnitSystem, language: &Language) -> Result<LocationWeatherGui, UIError> {
+        let current_conditions = match weather_forecast.current_weather.current_condition.first() {
+            Some(x) => x,
+            None => {return Err(UIError::DataError {msg: "No current conditions.".to_string()});}
+        };
+        let today: &ForecastDay = match weather_forecast.daily_forecast.weather.first() {
+            Some(x) => x,
+            None => {return Err(UIError::DataError {msg: "No daily forecast.".to_string()});}
+        };
+
+        Ok(LocationWeatherGui {
+            name: location.name.clone(),
+            current_temp: units.temperature(current_conditions.temp),
+            max_temp: units.temperature(today.max_temp),
+            min_temp: units.temperature(today.min_temp),
+            weather_desc: language.translate(&current_conditions.weather_desc),
+            weather_image: svg::Handle::from_path(current_conditions.weather_image.clone()),
+        })
+    }
+}
+
+impl HourlyWeatherGui {
+    fn view<'a>(self, language: Language, fonts: FontSizes) -> Element<'a, Message> {
+        let weather_svg: iced::widget::Svg<Renderer> = svg(self.weather_image.clone());
+            //.width(100)
+            //.height(100);
+
+        let day_row = 
+            column![
+                text(self.time).size(fonts.body).height(Length::FillPortion(1)),
+                weather_svg.height(Length::FillPortion(5)),
+                text(self.weather_desc).size(fonts.small).height(Length::FillPortion(1)),
+                text(format!("{} {}: {}", self.temp, language.translate("Feels like"), self.feels_like)).size(fonts.small).height(Length::FillPortion(1)),
+                text(format!("{}% {}, {}", self.chance_of_precip, language.translate("chance"), self.precip)).size(fonts.small).height(Length::FillPortion(1)),
+                row![
+                    svg(self.wind_arrow.clone()).width(Length::Fixed(20.0)).height(Length::Fixed(20.0)),
+                    text(&self.wind).size(fonts.small),
+                ].spacing(5)
+                .align_items(Alignment::Center)
+                .height(Length::FillPortion(1)),
+            ].spacing(10)
+            .align_items(Alignment::Center);
+
+        container(day_row)
+            .width(Length::Fill)
+            .height(Length::Fill)//Length::Fixed(183.0)) //Length::FillPortion(1))
+            .center_x()
+            .center_y()
+            .into()
+    }
+
+    async fn update(hour_weather: &ForecastHour, units: &UnitSystem, language: &Language) -> Result<HourlyWeatherGui, UIError> {
+        Ok(HourlyWeatherGui {
+            time: hour_weather.time.to_string(),
+            temp: units.temperature(hour_weather.temp),
+            feels_like: units.temperature(hour_weather.feels_like),
+            chance_of_precip: format_measurement(hour_weather.chance_of_precip()),
+            precip: units.precipitation(hour_weather.precip),
+            wind: get_wind(hour_weather.wind_speed, hour_weather.wind_gust, units, language),
+            wind_arrow: get_wind_arrow(hour_weather.wind_dir_degree),
+            weather_desc: language.translate(&hour_weather.weather_desc),
+            weather_image: svg::Handle::from_path(&hour_weather.weather_image)
+        })
+    }
+}
+
+fn get_average_precip(hourly_weather: &[ForecastHour]) -> Option<f32>  {
+    let precip_chances: Vec<f32> = hourly_weather.iter().filter_map(|hour| hour.chance_of_precip()).collect();
+    if precip_chances.is_empty() {
+        return None;
+    }
+
+    Some(precip_chances.iter().sum::<f32>() / precip_chances.len() as f32)
+}
+
+/// Gusts are left off when the provider doesn't report them.
+fn get_wind(wind_speed: Option<f32>, wind_gust: Option<f32>, units: &UnitSystem, language: &Language) -> String {
+    match wind_gust {
+        Some(_) => format!("{}, {} {}", units.wind_speed(wind_speed), language.translate("gusts"), units.wind_speed(wind_gust)),
+        None => units.wind_speed(wind_speed)
+    }
+}
+
+/// Wind direction is where the wind comes from, the arrow is turned to point where it's blowing.
+fn get_wind_arrow(wind_dir_degree: Option<f32>) -> svg::Handle {
+    let arrow_path: String = match wind_dir_degree {
+        Some(x) => format!("<path fill=\"white\" transform=\"rotate({} 15 15)\" d=\"M15 3 L23 26 L15 21 L7 26 Z\"/>", x + 180.0),
+        None => "".to_string()
+    };
+
+    svg::Handle::from_memory(format!("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 30 30\">{arrow_path}</svg>").into_bytes())
+}
+
+async fn update_all_weather(user_settings: UserSettings, providers: Arc<Vec<Box<dyn WeatherProvider>>>) -> Result<WeatherShim, UIError> {
+    let locations: Vec<Location> = user_settings.all_locations();
+    let units: UnitSystem = user_settings.unit_system();
+    let hour_12: bool = user_settings.hour_12;
+    let language: Language = user_settings.language;
+    let cache_max_age_hours: u32 = user_settings.cache_max_age_hours;
+
+    let (location, secondary_locations): (&Location, &[Location]) = match locations.split_first() {
+        Some(x) => x,
+        None => {return Err(UIError::DataError {msg: "No locations in settings.json.".to_string()});}
+    };
+
+    let weather_forecast: Weather = match get_weather(location, &hour_12, &cache_max_age_hours, &providers).await {
+        Ok(x) => x,
+        Err(req_error) => {return Err(UIError::APIError {error: req_error});}
+    };
+
+    let current_weather = match CurrentWeatherGui::update(&weather_forecast, &units, &language).await {
+        Ok(x) => x,
+        Err(curr_err) => {return Err(UIError::DataError {msg: format!("Error in getting current weather. {:?}", curr_err)});}
+    }; 
+
+    let mut daily_weather: Vec<DaysWeatherGui> = Vec::new();
+    for day in weather_forecast.daily_forecast.weather.iter().take(user_settings.forecast_days) {
+        let temp =  match DaysWeatherGui::update(day, &units, &language).await {
+            Ok(x) => x,
+            Err(day_err) => {return Err(UIError::DataError {msg: format!("Error in getting day {}'s weather. {:?}", day.date, day_err)});}
+        };
+
+        daily_weather.push(temp);
+    }
+
+    let mut hourly_weather: Vec<HourlyWeatherGui> = Vec::new();
+    // Forecast hours are in the location's own time, which may not be the mirror's.
+    let now: NaiveDateTime = match weather_forecast.utc_offset {
+        Some(offset) => Utc::now().with_timezone(&offset).naive_local(),
+        None => Local::now().naive_local()
+    };
+    let upcoming_hours: Vec<(NaiveDateTime, &ForecastHour)> = weather_forecast.daily_forecast.upcoming_hours(now);
+    for (_, hour) in upcoming_hours.iter().take(user_settings.hourly_slots) {
+        let temp = match HourlyWeatherGui::update(hour, &units, &language).await {
+            Ok(x) => x,
+            Err(hour_err) => {return Err(UIError::DataError {msg: format!("Error in getting hour {}'s weather. {:?}", hour.time, hour_err)});}
+        };
+
+        hourly_weather.push(temp);
+    }
+
+    let chart_hours: Vec<&ForecastHour> = upcoming_hours.iter()
+        .filter(|(start, _)| *start < now + Duration::hours(user_settings.chart_hours))
+        .map(|(_, hour)| *hour)
+        .collect();
+    let chart: ForecastChart = ForecastChart::new(&chart_hours, &units, &language);
+
+    let sky: SkyWidget = match weather_forecast.daily_forecast.weather.first().and_then(|day| day.astronomy.first()) {
+        Some(astronomy) => SkyWidget::new(astronomy, hour_12, weather_forecast.utc_offset, &language),
+        None => SkyWidget::default()
+    };
+
+    // A location that fails to update is left off rather than hiding the main forecast.
+    let mut other_locations: Vec<LocationWeatherGui> = Vec::new();
+    for other_location in secondary_locations {
+        let other_forecast: Weather = match get_weather(other_location, &hour_12, &cache_max_age_hours, &providers).await {
+            Ok(x) => x,
+            Err(req_error) => {
+                println!("Error in getting weather for {}: {:?}", other_location.name, req_error);
+                continue;
+            }
+        };
+
+        match LocationWeatherGui::update(other_location, &other_forecast, &units, &language).await {
+            Ok(x) => other_locations.push(x),
+            Err(location_err) => println!("Error in getting weather for {}: {:?}", other_location.name, location_err)
+        };
+    }
+
+    Ok(WeatherShim {
+        current_weather,
+        daily_weather,
+        hourly_weather,
+        chart,
+        sky,
+        alerts: weather_forecast.alerts.clone(),
+        location_name: location.name.clone(),
+        cached_at: weather_forecast.cached_at,
+        other_locations,
+    })
+    
+}
+
+struct AlertBannerStyle {
+    severity: AlertSeverity,
+}
+
+impl container::StyleSheet for AlertBannerStyle {
+    type Style = Theme;
+
+    fn appearance(&self, style: &Self::Style) -> container::Appearance {
+        let palette: theme::Palette = style.palette();
+        let (background, text_color) = match self.severity {
+            AlertSeverity::Extreme | AlertSeverity::Severe => (palette.danger, palette.background),
+            AlertSeverity::Moderate => (palette.primary, palette.text),
+            AlertSeverity::Minor | AlertSeverity::Unknown => (palette.background, palette.text),
+        };
+
+        container::Appearance {
+            text_color: Some(text_color),
+            background: Some(Background::Color(background)),
+            border_radius: 5.0,
+            border_width: 2.0,
+            border_color: palette.primary,
+        }
+    }
+}
+
+fn view_alert<'a>(alert: &WeatherAlert, hour_12: bool, language: Language, layout: &Layout) -> Element<'a, Message> {
+    let time_format: &str = match hour_12 {
+        true => "%a %I:%M %p",
+        false => "%a %H:%M"
+    };
+    let headline_size: u16 = match alert.is_warning() {
+        true => layout.font_sizes.body,
+        false => layout.font_sizes.small
+    };
+    let expires: String = alert.expires.format_localized(time_format, language.locale()).to_string();
+    let alert_time: String = match alert.is_upcoming() {
+        true => format!(
+            "{} {} {} {}",
+            language.translate("From"),
+            alert.effective.format_localized(time_format, language.locale()),
+            language.translate("until"),
+            expires
+        ),
+        false => format!("{} {}", language.translate("Until"), expires)
+    };
+
+    let mut alert_lines: Vec<Element<Message>> = vec![
+        text(&alert.headline).size(headline_size).into(),
+        text(alert_time).size(layout.font_sizes.small).into(),
+    ];
+    // Warnings say what to expect, watches and advisories stay on two lines.
+    let summary: String = alert.summary();
+    if alert.is_warning() && !summary.is_empty() {
+        alert_lines.push(text(summary).size(layout.font_sizes.small).into());
+    }
+
+    container(
+        Column::with_children(alert_lines)
+        .spacing(5)
+        .align_items(Alignment::Center)
+    )
+    .width(Length::Fill)
+    .padding(layout.padding)
+    .center_x()
+    .style(theme::Container::Custom(Box::new(AlertBannerStyle { severity: alert.severity })))
+    .into()
+}
+
+/// Doubles the wait after every failed attempt up to `RETRY_MAX_SECS`,
+/// with up to a quarter extra so mirrors sharing a network don't retry in lockstep.
+fn get_retry_delay(retry_count: u32) -> Duration {
+    let backoff_secs: u64 = RETRY_BASE_SECS.saturating_mul(2_u64.saturating_pow(retry_count)).min(RETRY_MAX_SECS);
+    let jitter_secs: u64 = rand::thread_rng().gen_range(0..=backoff_secs / 4);
+
+    Duration::seconds((backoff_secs + jitter_secs) as i64)
+}
+
+fn get_retry_status(next_retry: &Option<DateTime<Local>>, hour_12: bool, language: Language) -> String {
+    let retry_time: String = match (next_retry, hour_12) {
+        (Some(x), true) => x.format("%I:%M:%S %p").to_string(),
+        (Some(x), false) => x.format("%H:%M:%S").to_string(),
+        (None, _) => return "".to_string()
+    };
+
+    format!("{} {}", language.translate("Retrying at"), retry_time)
+}
+
+/// Shown while the forecast is coming from the cache instead of a provider.
+fn get_cache_age(cached_at: &DateTime<Local>, language: Language) -> String {
+    match (Local::now() - *cached_at).num_hours() {
+        0 => language.translate("Data is less than an hour old"),
+        1 => language.translate("Data is 1 hour old"),
+        hours => language.translate("Data is {} hours old").replace("{}", &hours.to_string())
+    }
+}